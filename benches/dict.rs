@@ -0,0 +1,62 @@
+//! Compare tokenization throughput of Thai tokenizer on different dictionary backends.
+//! 
+//! The dictionary is a synthetic Thai-like word list so the benchmark doesn't need any
+//! external dictionary file. Run it with `cargo bench`.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use tokenizer::{Tokenizer, dict::DoubleArray, th};
+
+const CONSONANTS: &[char] = &['ก', 'ข', 'ค', 'ง', 'จ', 'ช', 'ด', 'ต', 'ท', 'น', 'บ', 'ป', 'พ', 'ม', 'ย', 'ร', 'ล', 'ว', 'ส', 'ห', 'อ'];
+const VOWELS: &[&str] = &["", "า", "ิ", "ี", "ุ", "ู", "ำ"];
+const FINALS: &[&str] = &["", "", "น", "ง", "ม", "ก", "ด", "บ"];
+
+/// A simple linear congruential generator so the benchmark is reproducible.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 33) as usize) % bound
+    }
+}
+
+fn make_words(rng: &mut Lcg, count: usize) -> Vec<String> {
+    (0..count).map(|_| {
+        let syllables = 1 + rng.next(3);
+        (0..syllables).fold(String::new(), |mut word, _| {
+            word.push(CONSONANTS[rng.next(CONSONANTS.len())]);
+            word.push_str(VOWELS[rng.next(VOWELS.len())]);
+            word.push_str(FINALS[rng.next(FINALS.len())]);
+            word
+        })
+    }).collect()
+}
+
+fn make_text(rng: &mut Lcg, words: &[String], count: usize) -> String {
+    (0..count).fold(String::new(), |mut text, i| {
+        if i % 20 == 19 {
+            text.push(' ');
+        }
+        text.push_str(&words[rng.next(words.len())]);
+        text
+    })
+}
+
+fn bench_backends(c: &mut Criterion) {
+    let mut rng = Lcg(42);
+    let words = make_words(&mut rng, 20_000);
+    let text = make_text(&mut rng, &words, 2_000);
+
+    let radix = th::Tokenizer::from(words.as_slice());
+    let double_array = th::Tokenizer::with_dict(DoubleArray::from_words(&words));
+    assert_eq!(radix.tokenize(&text), double_array.tokenize(&text));
+
+    let mut group = c.benchmark_group("th_tokenize");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("radix_trie", |b| b.iter(|| radix.tokenize(&text)));
+    group.bench_function("double_array", |b| b.iter(|| double_array.tokenize(&text)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_backends);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+//! Compile bundled Thai word list into binary dictionary when `bundled-dict` feature is enabled.
+//!
+//! The compiler is shared with the crate itself by including its source file directly because
+//! a build script can't depend on the crate it builds.
+
+#[allow(dead_code)]
+#[path = "src/dict/compile/mod.rs"]
+mod compile;
+
+const WORD_LIST: &str = "data/th_bundled.txt";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/dict/compile/mod.rs");
+    println!("cargo:rerun-if-changed={}", WORD_LIST);
+
+    if std::env::var_os("CARGO_FEATURE_BUNDLED_DICT").is_none() {
+        return
+    }
+
+    let text = std::fs::read_to_string(WORD_LIST).expect("bundled word list is missing");
+    // The same grammar as `dict::compile_txt`: trimmed word per line, `#` comment and empty line are skipped
+    let words: Vec<&str> = text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    let out = std::path::Path::new(&std::env::var_os("OUT_DIR").unwrap()).join("th_bundled.bin");
+    std::fs::write(out, compile::compile(&words)).expect("unable to write bundled dictionary");
+}
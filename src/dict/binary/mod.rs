@@ -0,0 +1,310 @@
+//! A compact binary format of [SizedDict](../struct.SizedDict.html).
+//! 
+//! Building a dictionary from text file require adding each word into the tree one by one.
+//! It is slow on large dictionary. This module let user save a finalized dictionary once
+//! and load it back without rebuilding the tree.
+//! 
+//! The format is a flat table of nodes followed by a pool of node values.
+//! All numbers are little endian.
+//! 
+//! | Offset | Size | Description |
+//! |--------|------|-------------|
+//! | 0      | 8    | Magic bytes `TKNZDICT` |
+//! | 8      | 4    | Format version. Currently it is 2 |
+//! | 12     | 4    | Reserved flags. It is always 0 |
+//! | 16     | 8    | Number of words in dictionary |
+//! | 24     | 4    | Number of nodes |
+//! | 28     | 4    | Number of root nodes |
+//! | 32     | 8    | Length of value pool in bytes |
+//! | 40     | 8    | FNV-1a 64 bits checksum of everything after header |
+//! | 48     | 28 * nodes | Node table |
+//! | ...    | value pool length | Value pool |
+//! 
+//! Each node in node table is five `u32` followed by a `u64`. They are start offset of node value
+//! in value pool, length of node value, index of first child, number of childs, flags and frequency
+//! of the word that end at the node. Currently, only the lowest bit of flags is used. It is 1 if the
+//! node is terminal. The frequency of non-terminal node is 0.
+//! 
+//! Version 1 has no frequency so each node is only 20 bytes. It can still be read. Every word
+//! in version 1 has frequency 1.
+//! 
+//! The nodes are stored in breadth-first order. The root nodes are at the beginning of the table
+//! and childs of each node are stored next to each other in sorted order.
+
+use super::{Frequency, SizedDict, SizedNode};
+use super::compile::{write, NodeRecord, TERMINAL};
+use std::convert::TryFrom;
+use std::io::{Error, ErrorKind};
+
+pub(crate) use super::compile::{checksum, HEADER_LEN, MAGIC, NODE_LEN, NODE_LEN_V1, VERSION};
+
+/// Make an `InvalidData` error with given message.
+fn invalid(msg: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, msg)
+}
+
+/// Read a little endian `u32` at given offset. Caller must make sure that the bytes are long enough.
+#[inline(always)]
+pub(crate) fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    let mut buf = [0u8; 4];
+    buf.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_le_bytes(buf)
+}
+
+/// Read a little endian `u64` at given offset. Caller must make sure that the bytes are long enough.
+#[inline(always)]
+pub(crate) fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_le_bytes(buf)
+}
+
+/// Parsed header of binary dictionary.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Header {
+    /// Number of words in dictionary
+    pub(crate) entries: u64,
+    /// Number of nodes in node table
+    pub(crate) nodes: usize,
+    /// Number of root nodes
+    pub(crate) roots: usize,
+    /// Length of value pool in bytes
+    pub(crate) text_len: usize,
+    /// Size of each node in bytes. It depends on version.
+    pub(crate) node_len: usize,
+}
+
+impl Header {
+    /// Parse and validate header of given bytes.
+    /// 
+    /// It check magic bytes, version, total length and checksum but it doesn't
+    /// validate each node. Both version 1 and current version are accepted.
+    pub(crate) fn parse(bytes: &[u8]) -> std::io::Result<Header> {
+        if bytes.len() < HEADER_LEN || &bytes[0..8] != MAGIC {
+            return Err(invalid("Not a binary dictionary"));
+        }
+
+        let node_len = match read_u32(bytes, 8) {
+            1 => NODE_LEN_V1,
+            VERSION => NODE_LEN,
+            _ => return Err(invalid("Unsupported binary dictionary version"))
+        };
+
+        let header = Header {
+            entries: read_u64(bytes, 16),
+            nodes: read_u32(bytes, 24) as usize,
+            roots: read_u32(bytes, 28) as usize,
+            text_len: usize::try_from(read_u64(bytes, 32)).map_err(|_| invalid("Binary dictionary is truncated or has invalid length"))?,
+            node_len
+        };
+        // Every number come from the bytes so the length may overflow
+        let len = header.nodes.checked_mul(node_len)
+            .and_then(|table| table.checked_add(HEADER_LEN))
+            .and_then(|len| len.checked_add(header.text_len));
+
+        if header.roots > header.nodes || len != Some(bytes.len()) {
+            return Err(invalid("Binary dictionary is truncated or has invalid length"));
+        }
+
+        if checksum(&bytes[HEADER_LEN..]) != read_u64(bytes, 40) {
+            return Err(invalid("Binary dictionary checksum mismatch"));
+        }
+
+        Ok(header)
+    }
+
+    /// Offset of value pool in bytes.
+    pub(crate) fn pool_start(&self) -> usize {
+        HEADER_LEN + self.nodes * self.node_len
+    }
+}
+
+/// A node in node table.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RawNode {
+    pub(crate) text_start: usize,
+    pub(crate) text_len: usize,
+    pub(crate) first_child: usize,
+    pub(crate) child_count: usize,
+    pub(crate) terminal: bool,
+    pub(crate) frequency: u64,
+}
+
+impl RawNode {
+    /// Read a node at given index where each node is `node_len` bytes.
+    /// Caller must make sure that index is less than number of nodes.
+    #[inline(always)]
+    pub(crate) fn read(bytes: &[u8], node_len: usize, index: usize) -> RawNode {
+        let offset = HEADER_LEN + index * node_len;
+        let terminal = read_u32(bytes, offset + 16) & TERMINAL == TERMINAL;
+        RawNode {
+            text_start: read_u32(bytes, offset) as usize,
+            text_len: read_u32(bytes, offset + 4) as usize,
+            first_child: read_u32(bytes, offset + 8) as usize,
+            child_count: read_u32(bytes, offset + 12) as usize,
+            terminal,
+            // Version 1 has no frequency
+            frequency: if node_len == NODE_LEN_V1 {terminal as u64} else {read_u64(bytes, offset + 20)},
+        }
+    }
+
+    /// Validate that this node, at given index, point to valid value and childs.
+    /// 
+    /// Since nodes are stored in breadth-first order, childs must always come after the node and
+    /// childs of each node must come right after childs of previous node. `next_child` is the index
+    /// where childs of this node must begin. It is advanced past childs of this node.
+    /// This guarantee that every node except root nodes has exactly one parent so there's
+    /// neither cycle nor shared childs in the tree.
+    fn validate(&self, index: usize, header: &Header, next_child: &mut usize) -> std::io::Result<()> {
+        if !matches!(self.text_start.checked_add(self.text_len), Some(end) if end <= header.text_len) {
+            return Err(invalid("Node value is out of value pool"));
+        }
+
+        if self.child_count > 0 {
+            if !matches!(self.first_child.checked_add(self.child_count), Some(end) if end <= header.nodes) {
+                return Err(invalid("Node childs are out of node table"));
+            }
+
+            if self.first_child <= index || self.first_child != *next_child {
+                return Err(invalid("Node childs are not in breadth-first order"));
+            }
+
+            *next_child += self.child_count;
+        }
+
+        Ok(())
+    }
+}
+
+/// Validate every node in node table of given bytes and return number of words along with
+/// sum of their frequency.
+/// 
+/// Every node must point to valid UTF-8 value and nodes must form a tree in breadth-first order.
+/// Once it pass, each node can be read without any further check.
+pub(crate) fn validate_nodes(bytes: &[u8], header: &Header) -> std::io::Result<(u64, u64)> {
+    let pool_start = header.pool_start();
+    let mut next_child = header.roots;
+    let mut entries = 0;
+    let mut total_frequency = 0u64;
+
+    for index in 0..header.nodes {
+        let raw = RawNode::read(bytes, header.node_len, index);
+        raw.validate(index, header, &mut next_child)?;
+
+        let start = pool_start + raw.text_start;
+        if std::str::from_utf8(&bytes[start..start + raw.text_len]).is_err() {
+            return Err(invalid("Node value is not valid UTF-8"));
+        }
+
+        entries += raw.terminal as u64;
+        total_frequency = total_frequency.saturating_add(raw.frequency);
+    }
+
+    if next_child != header.nodes {
+        return Err(invalid("Some nodes are not reachable from root nodes"));
+    }
+
+    if entries != header.entries {
+        return Err(invalid("Number of words doesn't match with header"));
+    }
+
+    Ok((entries, total_frequency))
+}
+
+impl<V: Frequency> SizedDict<V> {
+    /// Serialize this dictionary into binary format.
+    /// Only the words and [frequency](trait.Frequency.html) of each word are serialized.
+    /// Other part of payload, e.g. tags of [WordInfo](struct.WordInfo.html), is not part of the format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Breadth-first traversal so childs of each node are next to each other.
+        let mut queue: std::collections::VecDeque<&SizedNode<V>> = self.root.iter().collect();
+
+        write(self.root.len(), std::iter::from_fn(|| {
+            let node = queue.pop_front()?;
+            queue.extend(node.childs.iter());
+
+            Some(NodeRecord {
+                value: &node.value,
+                child_count: node.childs.len(),
+                terminal: node.terminal.is_some(),
+                frequency: node.terminal.as_ref().map_or(0, Frequency::frequency)
+            })
+        }))
+    }
+
+    /// Save this dictionary in binary format to given path.
+    /// 
+    /// It return an error with the file name if the file cannot be written.
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> crate::Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_bytes()).map_err(|e| crate::Error::io(path, e))
+    }
+}
+
+/// Deserialize a dictionary from binary format. `payload` make a payload of each word from its frequency.
+pub(super) fn decode<V, F: Fn(u64) -> V>(bytes: &[u8], payload: F) -> std::io::Result<SizedDict<V>> {
+    let header = Header::parse(bytes)?;
+    let (entries, _) = validate_nodes(bytes, &header)?;
+    let pool_start = header.pool_start();
+    let mut nodes: Vec<Option<SizedNode<V>>> = Vec::with_capacity(header.nodes);
+    nodes.resize_with(header.nodes, || None);
+
+    // Childs always come after its parent so build nodes from the last one. Each node is
+    // taken by exactly one parent as validated. It never recurse so a deep tree can't overflow the stack.
+    for index in (0..header.nodes).rev() {
+        let raw = RawNode::read(bytes, header.node_len, index);
+        let start = pool_start + raw.text_start;
+        let childs = nodes[raw.first_child..raw.first_child + raw.child_count].iter_mut().map(|c| c.take().unwrap()).collect();
+
+        nodes[index] = Some(SizedNode {
+            childs,
+            terminal: raw.terminal.then(|| payload(raw.frequency)),
+            value: std::str::from_utf8(&bytes[start..start + raw.text_len]).unwrap().to_owned()
+        });
+    }
+
+    Ok(SizedDict {
+        root: nodes.into_iter().take(header.roots).map(Option::unwrap).collect(),
+        len: entries as usize
+    })
+}
+
+impl SizedDict {
+    /// Deserialize a dictionary from binary format.
+    /// 
+    /// Only the words are kept. Use [from_bytes_with_frequency](struct.SizedDict.html#method.from_bytes_with_frequency)
+    /// to also keep frequency of each word.
+    /// It return an error of kind `InvalidData` if the bytes is not a valid binary dictionary.
+    pub fn from_bytes(bytes: &[u8]) -> std::io::Result<SizedDict> {
+        decode(bytes, |_| ())
+    }
+
+    /// Load a dictionary in binary format from given path. Only the words are kept.
+    /// 
+    /// It return an error with the file name if the file cannot be read. The error is of kind
+    /// `InvalidData` if the file is not a valid binary dictionary.
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> crate::Result<SizedDict> {
+        super::load_compiled(path.as_ref())
+    }
+}
+
+impl SizedDict<u64> {
+    /// Deserialize a dictionary from binary format along with frequency of each word.
+    /// 
+    /// Every word of version 1 format has frequency 1.
+    /// It return an error of kind `InvalidData` if the bytes is not a valid binary dictionary.
+    pub fn from_bytes_with_frequency(bytes: &[u8]) -> std::io::Result<SizedDict<u64>> {
+        decode(bytes, |frequency| frequency)
+    }
+
+    /// Load a dictionary in binary format from given path along with frequency of each word.
+    /// 
+    /// It return an error with the file name if the file cannot be read. The error is of kind
+    /// `InvalidData` if the file is not a valid binary dictionary.
+    pub fn load_with_frequency<P: AsRef<std::path::Path>>(path: P) -> crate::Result<SizedDict<u64>> {
+        super::load_compiled(path.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,174 @@
+use super::*;
+use crate::dict::Dict;
+
+#[test]
+fn test_round_trip() {
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    let bytes = dict.to_bytes();
+    let header = Header::parse(&bytes).unwrap();
+
+    assert_eq!(header.entries, 12);
+    assert_eq!(header.roots, 3);
+    assert_eq!(SizedDict::from_bytes(&bytes).unwrap(), dict);
+}
+
+#[test]
+fn test_empty_dict() {
+    let dict: SizedDict = Dict::new().into();
+    assert_eq!(SizedDict::from_bytes(&dict.to_bytes()).unwrap(), dict);
+}
+
+#[test]
+fn test_save_load() {
+    let path = std::env::temp_dir().join(format!("tokenizer_test_save_load_{}.bin", std::process::id()));
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    dict.save(&path).unwrap();
+    let loaded = SizedDict::load(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.unwrap(), dict);
+}
+
+#[test]
+fn test_load_error_path() {
+    let path = std::path::Path::new("data/th.txt");
+    let err = SizedDict::load(path).unwrap_err();
+    assert!(matches!(&err, crate::Error::Io {path: Some(p), source} if p == path && source.kind() == std::io::ErrorKind::InvalidData));
+
+    let path = std::path::Path::new("data/missing.bin");
+    let err = SizedDict::load_with_frequency(path).unwrap_err();
+    assert!(matches!(&err, crate::Error::Io {path: Some(p), source} if p == path && source.kind() == std::io::ErrorKind::NotFound));
+}
+
+#[test]
+fn test_invalid_bytes() {
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    let bytes = dict.to_bytes();
+
+    // Not a dictionary
+    assert_eq!(SizedDict::from_bytes(b"hello").unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+
+    // Truncated
+    assert!(SizedDict::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+    // Corrupted value pool
+    let mut corrupted = bytes.clone();
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 0xff;
+    assert_eq!(SizedDict::from_bytes(&corrupted).unwrap_err().to_string(), "Binary dictionary checksum mismatch");
+
+    // Unknown version
+    let mut future = bytes;
+    future[8] = 3;
+    assert!(SizedDict::from_bytes(&future).is_err());
+}
+
+/// Recompute checksum of given bytes after it is modified.
+fn reseal(bytes: &mut [u8]) {
+    let sum = checksum(&bytes[HEADER_LEN..]);
+    bytes[40..HEADER_LEN].copy_from_slice(&sum.to_le_bytes());
+}
+
+#[test]
+fn test_crafted_header() {
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    let bytes = dict.to_bytes();
+
+    // A length that overflow is rejected instead of wrapping around
+    let mut crafted = bytes.clone();
+    crafted[24..28].copy_from_slice(&u32::MAX.to_le_bytes());
+    crafted[32..40].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert_eq!(Header::parse(&crafted).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+
+    let mut crafted = bytes;
+    crafted[32..40].copy_from_slice(&(u64::MAX - 100).to_le_bytes());
+    assert_eq!(Header::parse(&crafted).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_crafted_nodes() {
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    let bytes = dict.to_bytes();
+    let node = |index: usize| HEADER_LEN + index * NODE_LEN;
+    let parents: Vec<usize> = (0..read_u32(&bytes, 24) as usize).filter(|&i| read_u32(&bytes, node(i) + 12) > 0).take(2).collect();
+    let (a, b) = (node(parents[0]), node(parents[1]));
+
+    // Two nodes share the same childs
+    let mut crafted = bytes.clone();
+    let childs = crafted[a + 8..a + 16].to_vec();
+    crafted[b + 8..b + 16].copy_from_slice(&childs);
+    reseal(&mut crafted);
+    assert_eq!(SizedDict::from_bytes(&crafted).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    assert!(crate::dict::DictView::new(&crafted).is_err());
+
+    // A node that is nobody's child
+    let mut crafted = bytes;
+    let count = read_u32(&crafted, a + 12);
+    crafted[a + 12..a + 16].copy_from_slice(&(count - 1).to_le_bytes());
+    reseal(&mut crafted);
+    assert!(SizedDict::from_bytes(&crafted).is_err());
+}
+
+fn word_info_dict() -> SizedDict<crate::dict::WordInfo> {
+    let mut dict = Dict::default();
+    dict.insert("กา", crate::dict::WordInfo {frequency: 100, tags: vec!["NOUN".to_owned()]});
+    dict.insert("กาก", crate::dict::WordInfo {frequency: 3, tags: vec![]});
+    dict.into()
+}
+
+/// Convert given bytes of current version into version 1 by dropping frequency of each node.
+fn to_v1(bytes: &[u8]) -> Vec<u8> {
+    let nodes = read_u32(bytes, 24) as usize;
+    let mut v1 = bytes[..HEADER_LEN].to_vec();
+    v1[8..12].copy_from_slice(&1u32.to_le_bytes());
+
+    for index in 0..nodes {
+        let offset = HEADER_LEN + index * NODE_LEN;
+        v1.extend_from_slice(&bytes[offset..offset + NODE_LEN_V1]);
+    }
+
+    v1.extend_from_slice(&bytes[HEADER_LEN + nodes * NODE_LEN..]);
+    reseal(&mut v1);
+    v1
+}
+
+#[test]
+fn test_frequency() {
+    use crate::dict::{DictView, Lookup};
+
+    let dict = word_info_dict();
+    let bytes = dict.to_bytes();
+    let mut expected = Vec::new();
+    dict.frequencies_prefix("กากบาท", 0, &mut expected);
+    assert_eq!(expected, vec![(6, 100), (9, 3)]);
+
+    let view = DictView::new(&bytes).unwrap();
+    let mut results = Vec::new();
+    view.frequencies_prefix("กากบาท", 0, &mut results);
+    assert_eq!(results, expected);
+    assert_eq!(view.total_frequency(), 103);
+
+    let loaded = SizedDict::from_bytes_with_frequency(&bytes).unwrap();
+    assert_eq!(loaded.get("กา"), Some(&100));
+    assert_eq!(loaded.total_frequency(), 103);
+    // Words only
+    assert_eq!(SizedDict::from_bytes(&bytes).unwrap().total_frequency(), 2);
+}
+
+#[test]
+fn test_version_1() {
+    use crate::dict::{DictView, Lookup};
+
+    let dict = word_info_dict();
+    let v1 = to_v1(&dict.to_bytes());
+    let header = Header::parse(&v1).unwrap();
+    assert_eq!(header.node_len, NODE_LEN_V1);
+
+    // Version 1 has no frequency so every word has frequency 1
+    let view = DictView::new(&v1).unwrap();
+    let mut results = Vec::new();
+    view.frequencies_prefix("กากบาท", 0, &mut results);
+    assert_eq!(results, vec![(6, 1), (9, 1)]);
+    assert_eq!(SizedDict::from_bytes_with_frequency(&v1).unwrap().get("กาก"), Some(&1));
+    assert_eq!(SizedDict::from_bytes(&v1).unwrap(), SizedDict::from_bytes(&dict.to_bytes()).unwrap());
+}
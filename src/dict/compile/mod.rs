@@ -0,0 +1,152 @@
+//! Compile a list of words directly into [binary](../binary/index.html) format.
+//!
+//! It doesn't build any [Dict](../struct.Dict.html). Words are sorted then each group of words
+//! that share the same prefix become a node. The nodes are written by the same writer as
+//! [SizedDict::to_bytes](../struct.SizedDict.html#method.to_bytes) so the result is exactly
+//! the same as saving a `SizedDict` of the same words.
+//!
+//! This module only use `std` so a build script can include it by `#[path]` attribute.
+
+/// Magic bytes to identify the format.
+pub(crate) const MAGIC: &[u8; 8] = b"TKNZDICT";
+/// Current version of the format.
+pub(crate) const VERSION: u32 = 2;
+/// Size of header in bytes.
+pub(crate) const HEADER_LEN: usize = 48;
+/// Size of each node in node table in bytes.
+pub(crate) const NODE_LEN: usize = 28;
+/// Size of each node in version 1 which has no frequency.
+pub(crate) const NODE_LEN_V1: usize = 20;
+/// A flag bit to mark terminal node.
+pub(crate) const TERMINAL: u32 = 1;
+
+/// Calculate FNV-1a 64 bits hash of given bytes.
+pub(crate) fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// A node being compiled. It is a range of sorted words that share first `depth + len` bytes.
+struct Group {
+    start: usize,
+    end: usize,
+    /// Length of prefix that is already taken by ancestors of this node
+    depth: usize,
+    /// Length of value of this node
+    len: usize,
+}
+
+/// Return a length in bytes of common prefix of both text. It is always at char boundary.
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.chars().zip(b.chars()).take_while(|(x, y)| x == y).map(|(x, _)| x.len_utf8()).sum()
+}
+
+/// Split sorted words in given range into sibling nodes. Every word must be longer than `depth`.
+fn split(words: &[&str], start: usize, end: usize, depth: usize) -> Vec<Group> {
+    let mut groups = Vec::new();
+    let mut i = start;
+
+    while i < end {
+        let first = words[i][depth..].chars().next().unwrap();
+        let j = (i + 1..end).find(|&j| !words[j][depth..].starts_with(first)).unwrap_or(end);
+
+        groups.push(Group {
+            start: i,
+            end: j,
+            depth,
+            len: common_prefix_len(&words[i][depth..], &words[j - 1][depth..])
+        });
+        i = j;
+    }
+
+    groups
+}
+
+/// A node to be written by [write](fn.write.html).
+pub(crate) struct NodeRecord<'a> {
+    pub(crate) value: &'a str,
+    pub(crate) child_count: usize,
+    pub(crate) terminal: bool,
+    /// Frequency of the word that end at this node. It is ignored if the node is not terminal.
+    pub(crate) frequency: u64,
+}
+
+/// Write a binary dictionary of given nodes. It is the only writer of the format.
+/// 
+/// Nodes must be in breadth-first order and the first `root_count` nodes are root nodes.
+/// Childs of each node are right after childs of previous node so index of the first child
+/// of each node is known by counting.
+pub(crate) fn write<'a, I: IntoIterator<Item=NodeRecord<'a>>>(root_count: usize, nodes: I) -> Vec<u8> {
+    let mut table = Vec::new();
+    let mut pool = Vec::new();
+    let mut entries = 0u64;
+    let mut node_count = root_count;
+
+    for node in nodes {
+        let first_child = node_count;
+        node_count += node.child_count;
+        entries += node.terminal as u64;
+
+        table.extend_from_slice(&(pool.len() as u32).to_le_bytes());
+        table.extend_from_slice(&(node.value.len() as u32).to_le_bytes());
+        table.extend_from_slice(&(first_child as u32).to_le_bytes());
+        table.extend_from_slice(&(node.child_count as u32).to_le_bytes());
+        table.extend_from_slice(&(if node.terminal {TERMINAL} else {0}).to_le_bytes());
+        table.extend_from_slice(&(if node.terminal {node.frequency} else {0}).to_le_bytes());
+        pool.extend_from_slice(node.value.as_bytes());
+    }
+
+    let mut bytes = Vec::with_capacity(HEADER_LEN + table.len() + pool.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&entries.to_le_bytes());
+    bytes.extend_from_slice(&(node_count as u32).to_le_bytes());
+    bytes.extend_from_slice(&(root_count as u32).to_le_bytes());
+    bytes.extend_from_slice(&(pool.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&0u64.to_le_bytes()); // checksum placeholder
+    bytes.extend_from_slice(&table);
+    bytes.extend_from_slice(&pool);
+
+    let sum = checksum(&bytes[HEADER_LEN..]);
+    bytes[40..HEADER_LEN].copy_from_slice(&sum.to_le_bytes());
+
+    bytes
+}
+
+/// Compile given words into binary dictionary.
+///
+/// The words don't need to be sorted. An empty word and a duplicate word are ignored.
+/// Every word has frequency 1.
+pub(crate) fn compile<S: AsRef<str>>(words: &[S]) -> Vec<u8> {
+    let mut words: Vec<&str> = words.iter().map(AsRef::as_ref).filter(|w| !w.is_empty()).collect();
+    words.sort_unstable();
+    words.dedup();
+
+    let roots = split(&words, 0, words.len(), 0);
+    let root_count = roots.len();
+    // Breadth-first traversal so childs of each node are next to each other.
+    let mut queue: std::collections::VecDeque<Group> = roots.into();
+
+    write(root_count, std::iter::from_fn(|| {
+        let group = queue.pop_front()?;
+        let prefix = group.depth + group.len;
+        // Words are sorted so the word that end at this node, if any, is the first one.
+        let word = words[group.start];
+        let terminal = word.len() == prefix;
+        let childs = split(&words, group.start + terminal as usize, group.end, prefix);
+        let record = NodeRecord {
+            value: &word[group.depth..prefix],
+            child_count: childs.len(),
+            terminal,
+            frequency: 1
+        };
+
+        queue.extend(childs);
+        Some(record)
+    }))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,21 @@
+use super::*;
+use crate::dict::{Dict, SizedDict};
+
+#[test]
+fn test_same_as_sized_dict() {
+    let words = ["กรรม", "กร", "กรรมกร", "กรม", "ภาษา", "กรรมการ", "กรรม", "", "a", "ab"];
+    let mut dict = Dict::new();
+    words.iter().filter(|w| !w.is_empty()).for_each(|w| dict.add(w));
+    assert_eq!(compile(&words), SizedDict::from(dict).to_bytes());
+
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    let words: Vec<String> = std::fs::read_to_string("data/th.txt").unwrap().lines().rev().map(str::to_owned).collect();
+    assert_eq!(compile(&words), dict.to_bytes());
+}
+
+#[test]
+fn test_empty() {
+    let bytes = compile::<&str>(&[]);
+    assert_eq!(bytes.len(), HEADER_LEN);
+    assert!(SizedDict::from_bytes(&bytes).unwrap().is_empty());
+}
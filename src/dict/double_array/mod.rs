@@ -0,0 +1,291 @@
+//! A double-array trie implementation of dictionary.
+//! 
+//! [SizedDict](../struct.SizedDict.html) need to compare every child of a node with the text
+//! to find the next node. It is linear to number of childs on every level.
+//! A double-array trie need only one array lookup for each byte of the text.
+//! 
+//! The trie is built on UTF-8 bytes of each word. Each state `s` has a `base` value.
+//! A transition from `s` by byte `b` goes to state `t = base[s] + b + 1` if `check[t] == s`.
+//! A state with `base` 0 has no transition.
+
+use super::{Frequency, Lookup, SizedDict};
+
+/// A `check` value of unused slot.
+const UNUSED: u32 = u32::MAX;
+
+/// An end of the list of unused slots.
+const NONE: usize = usize::MAX;
+
+/// An immutable dictionary stored as double-array trie.
+/// 
+/// It give the same lookup result as [SizedDict](../struct.SizedDict.html) that has the same words.
+/// It is generally faster to lookup than `SizedDict` but it take more time to build.
+#[derive(Debug, PartialEq)]
+pub struct DoubleArray {
+    /// A base of transition of each state. 0 mean no transition.
+    base: Box<[u32]>,
+    /// A parent state of each state. `UNUSED` mean no state is at this index.
+    check: Box<[u32]>,
+    /// A flag telling whether a word end at each state.
+    terminal: Box<[bool]>,
+    /// Frequency of the word that end at each state. It is 0 at non-terminal state.
+    frequency: Box<[u64]>,
+    /// Number of words in dictionary.
+    len: usize,
+    /// Sum of frequency of every word.
+    total_frequency: u64,
+}
+
+/// A mutable arrays used while building [DoubleArray](struct.DoubleArray.html)
+struct Builder {
+    base: Vec<u32>,
+    check: Vec<u32>,
+    terminal: Vec<bool>,
+    frequency: Vec<u64>,
+    /// Next unused slot of each unused slot in ascending order. It is `NONE` at the last one.
+    next_free: Vec<usize>,
+    /// Previous unused slot of each unused slot. It is `NONE` at the first one.
+    prev_free: Vec<usize>,
+    /// The first unused slot or `NONE` if every slot is used.
+    first_free: usize,
+    /// The last unused slot or `NONE` if every slot is used.
+    last_free: usize,
+}
+
+impl Builder {
+    /// Make sure that every array can be indexed up to given index.
+    /// New slots are appended to the list of unused slots.
+    fn ensure(&mut self, index: usize) {
+        if index >= self.check.len() {
+            let old_len = self.check.len();
+            let len = (index + 1).max(old_len * 2);
+            self.base.resize(len, 0);
+            self.check.resize(len, UNUSED);
+            self.terminal.resize(len, false);
+            self.frequency.resize(len, 0);
+            self.next_free.extend((old_len + 1..=len).map(|next| if next < len {next} else {NONE}));
+            self.prev_free.extend((old_len..len).map(|slot| slot.wrapping_sub(1)));
+            self.prev_free[old_len] = self.last_free;
+
+            if self.last_free == NONE {
+                self.first_free = old_len;
+            } else {
+                self.next_free[self.last_free] = old_len;
+            }
+
+            self.last_free = len - 1;
+        }
+    }
+
+    /// Mark given unused slot as used by `state` and unlink it from the list of unused slots.
+    fn take(&mut self, slot: usize, state: usize) {
+        let (prev, next) = (self.prev_free[slot], self.next_free[slot]);
+
+        if prev == NONE {
+            self.first_free = next;
+        } else {
+            self.next_free[prev] = next;
+        }
+
+        if next == NONE {
+            self.last_free = prev;
+        } else {
+            self.prev_free[next] = prev;
+        }
+
+        self.check[slot] = state as u32;
+    }
+
+    /// Find a base value where every `base + label` is unused.
+    /// `labels` must be sorted in ascending order.
+    /// 
+    /// Only unused slots are tried as the slot of the first label so used slots are never scanned.
+    fn find_base(&mut self, labels: &[usize]) -> usize {
+        let (first, last) = (labels[0], labels[labels.len() - 1]);
+        let mut pos = self.first_free;
+
+        loop {
+            if pos == NONE {
+                // Every unused slot is tried. The next one is the first slot of grown arrays.
+                pos = self.check.len();
+                self.ensure(pos);
+            }
+
+            // Base must be at least 1
+            if pos > first {
+                let base = pos - first;
+                self.ensure(base + last);
+
+                if labels[1..].iter().all(|label| self.check[base + label] == UNUSED) {
+                    return base
+                }
+            }
+
+            pos = self.next_free[pos];
+        }
+    }
+
+    /// Add all given words as descendant of `state`.
+    /// 
+    /// All `words` must be sorted, unique and share the same prefix of `depth` bytes which
+    /// is already represented by `state`. Each word come with its frequency.
+    fn insert(&mut self, state: usize, words: &[(&[u8], u64)], depth: usize) {
+        let mut words = words;
+
+        // Sorted words guarantee that the word that end at this state come first.
+        if let Some((first, frequency)) = words.first() {
+            if first.len() == depth {
+                self.terminal[state] = true;
+                self.frequency[state] = *frequency;
+                words = &words[1..];
+            }
+        }
+
+        if words.is_empty() {
+            return
+        }
+
+        // Group words by the byte at `depth`. Each group become a child state.
+        let mut groups = Vec::new();
+        let mut start = 0;
+
+        for i in 1..=words.len() {
+            if i == words.len() || words[i].0[depth] != words[start].0[depth] {
+                groups.push((words[start].0[depth] as usize + 1, start, i)); // label 0 is reserved
+                start = i;
+            }
+        }
+
+        let labels: Vec<usize> = groups.iter().map(|(label, _, _)| *label).collect();
+        let base = self.find_base(&labels);
+        self.base[state] = base as u32;
+
+        for label in labels.iter() {
+            self.take(base + label, state);
+        }
+
+        for (label, start, end) in groups {
+            self.insert(base + label, &words[start..end], depth + 1);
+        }
+    }
+}
+
+impl DoubleArray {
+    /// Build a double-array trie from given words. The words can be in any order and may contain duplicate.
+    /// Every word has frequency 1.
+    pub fn from_words<I, S>(words: I) -> DoubleArray where I: IntoIterator<Item=S>, S: AsRef<str> {
+        let mut words: Vec<String> = words.into_iter().map(|w| w.as_ref().to_owned()).collect();
+        words.sort_unstable();
+        words.dedup();
+        DoubleArray::build(words.iter().map(|w| (w.as_bytes(), 1)).collect())
+    }
+
+    /// Build a double-array trie from sorted unique words along with frequency of each word.
+    fn build(words: Vec<(&[u8], u64)>) -> DoubleArray {
+        let mut builder = Builder {
+            base: vec![0],
+            check: vec![0], // root state is always used
+            terminal: vec![false],
+            frequency: vec![0],
+            next_free: vec![NONE],
+            prev_free: vec![NONE],
+            first_free: NONE,
+            last_free: NONE,
+        };
+        builder.insert(0, &words, 0);
+
+        // Trim trailing unused slots
+        let used = builder.check.iter().rposition(|c| *c != UNUSED).unwrap_or(0) + 1;
+        builder.base.truncate(used);
+        builder.check.truncate(used);
+        builder.terminal.truncate(used);
+        builder.frequency.truncate(used);
+
+        DoubleArray {
+            base: builder.base.into_boxed_slice(),
+            check: builder.check.into_boxed_slice(),
+            terminal: builder.terminal.into_boxed_slice(),
+            total_frequency: builder.frequency.iter().fold(0u64, |sum, f| sum.saturating_add(*f)),
+            frequency: builder.frequency.into_boxed_slice(),
+            len: words.len()
+        }
+    }
+
+    /// Number of words in this dictionary.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return true if there's no word in this dictionary.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Move from `state` by given byte. It return `None` if there's no such transition.
+    #[inline(always)]
+    fn transit(&self, state: usize, byte: u8) -> Option<usize> {
+        let base = self.base[state] as usize;
+
+        if base == 0 {
+            return None
+        }
+
+        let next = base + byte as usize + 1;
+
+        if next < self.check.len() && self.check[next] as usize == state {
+            Some(next)
+        } else {
+            None
+        }
+    }
+}
+
+/// Build a double-array trie that has the same words and frequencies as given [SizedDict](../struct.SizedDict.html).
+impl<V: Frequency> From<&SizedDict<V>> for DoubleArray {
+    fn from(dict: &SizedDict<V>) -> DoubleArray {
+        // Words from dictionary are already sorted and unique
+        let entries: Vec<(String, u64)> = dict.entries().map(|(word, payload)| (word, payload.frequency())).collect();
+        DoubleArray::build(entries.iter().map(|(word, frequency)| (word.as_bytes(), *frequency)).collect())
+    }
+}
+
+impl DoubleArray {
+    /// Call `f` with an offset of the end of every word that is a prefix of `&value[offset..]`.
+    fn visit_terminals<F>(&self, value: &str, offset: usize, mut f: F) where F: FnMut(usize, u64) {
+        let mut state = 0;
+
+        if self.terminal[state] {
+            // An empty word is in dictionary
+            f(offset, self.frequency[state]);
+        }
+
+        for (i, byte) in value.as_bytes()[offset..].iter().enumerate() {
+            match self.transit(state, *byte) {
+                Some(next) => state = next,
+                None => break
+            }
+
+            // Every word is valid UTF-8 so terminal state is always at char boundary
+            if self.terminal[state] {
+                f(offset + i + 1, self.frequency[state]);
+            }
+        }
+    }
+}
+
+impl Lookup for DoubleArray {
+    fn terminals_prefix(&self, value: &str, offset: usize, results: &mut Vec<usize>) {
+        self.visit_terminals(value, offset, |end, _| results.push(end))
+    }
+
+    fn frequencies_prefix(&self, value: &str, offset: usize, results: &mut Vec<(usize, u64)>) {
+        self.visit_terminals(value, offset, |end, frequency| results.push((end, frequency)))
+    }
+
+    fn total_frequency(&self) -> u64 {
+        self.total_frequency
+    }
+}
+
+#[cfg(test)]
+mod tests;
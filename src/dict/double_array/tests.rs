@@ -0,0 +1,104 @@
+use super::*;
+use crate::dict::Dict;
+
+#[test]
+fn test_consistent_with_sized_dict() {
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    let double_array = DoubleArray::from(&dict);
+    let mut expected = Vec::new();
+    let mut actual = Vec::new();
+
+    assert_eq!(double_array.len(), 12);
+
+    for text in ["กรรมการบ้านเอาการเอางาน", "อาจารย์กรณ์การละเล่นการบูรณ์", "xyzกรรมกรabc"].iter() {
+        for (offset, _) in text.char_indices() {
+            expected.clear();
+            actual.clear();
+            dict.terminals_prefix(text, offset, &mut expected);
+            double_array.terminals_prefix(text, offset, &mut actual);
+            assert_eq!(expected, actual, "Lookup mismatch on {} at {}", text, offset);
+        }
+    }
+}
+
+#[test]
+fn test_from_words() {
+    let dict = DoubleArray::from_words(vec!["ab", "a", "abc", "b", "ab", "ä"]);
+    let mut results = Vec::new();
+
+    assert_eq!(dict.len(), 5);
+    dict.terminals_prefix("xabcd", 1, &mut results);
+    assert_eq!(results, vec![2, 3, 4]);
+
+    results.clear();
+    dict.terminals_prefix("äb", 0, &mut results);
+    assert_eq!(results, vec![2]);
+
+    results.clear();
+    dict.terminals_prefix("c", 0, &mut results);
+    assert!(results.is_empty());
+}
+
+#[test]
+fn test_empty() {
+    let dict = DoubleArray::from_words(Vec::<&str>::new());
+    let mut results = Vec::new();
+    dict.terminals_prefix("abc", 0, &mut results);
+
+    assert!(dict.is_empty());
+    assert!(results.is_empty());
+}
+
+#[test]
+fn test_tokenizer() {
+    use crate::tokenizer::Tokenizer;
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    let tokenizer = crate::th::Tokenizer::with_dict(DoubleArray::from(&dict));
+
+    assert_eq!(tokenizer.tokenize("เอากรรมกรที่เอาการเอางาน"), vec!["เอา", "กรรมกร", "ที่", "เอาการเอางาน"]);
+}
+
+#[test]
+fn test_frequency() {
+    use crate::dict::WordInfo;
+
+    let mut dict = Dict::default();
+    dict.insert("กา", WordInfo {frequency: 100, tags: vec![]});
+    dict.insert("กาก", WordInfo {frequency: 3, tags: vec![]});
+    let double_array = DoubleArray::from(&SizedDict::from(dict));
+    let mut results = Vec::new();
+    double_array.frequencies_prefix("กากบาท", 0, &mut results);
+    assert_eq!(results, vec![(6, 100), (9, 3)]);
+    assert_eq!(double_array.total_frequency(), 103);
+
+    // Words without payload count once
+    assert_eq!(DoubleArray::from_words(["กา", "กาก"]).total_frequency(), 2);
+}
+
+#[test]
+fn test_large_build() {
+    // Pseudo random Thai words so the trie has many states with scattered labels
+    let mut seed = 1u64;
+    let words: Vec<String> = (0..50_000).map(|_| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let len = 2 + (seed >> 33) as usize % 9;
+        (0..len).map(|i| char::from_u32(0xe01 + (seed >> (i * 3)) as u32 % 0x2e).unwrap()).collect()
+    }).collect();
+
+    let start = std::time::Instant::now();
+    let double_array = DoubleArray::from_words(&words);
+    // Building is roughly linear. A slot by slot search took minutes.
+    assert!(start.elapsed() < std::time::Duration::from_secs(30), "{:?}", start.elapsed());
+
+    let mut unique = words.clone();
+    unique.sort_unstable();
+    unique.dedup();
+    assert_eq!(double_array.len(), unique.len());
+
+    let mut results = Vec::new();
+    for word in words.iter() {
+        results.clear();
+        double_array.terminals_prefix(word, 0, &mut results);
+        assert_eq!(results.last(), Some(&word.len()), "{} is missing", word);
+    }
+}
@@ -0,0 +1,147 @@
+//! A dictionary made of an ordered stack of dictionaries.
+//! 
+//! A shared base dictionary often need a few more domain words and need to suppress a few
+//! words that cause bad splits. Instead of merging word lists and rebuilding the whole trie,
+//! [LayeredDict](struct.LayeredDict.html) keep each of them as a separate layer and query
+//! every layer on each lookup.
+//! 
+//! Layers are applied from bottom to top. An addition layer add its words and a removal layer
+//! remove its words from everything below it. So a word that is removed can be added back by
+//! a later addition layer.
+
+use super::Lookup;
+
+/// What a layer does to words from layers below it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayerKind {
+    /// Add every word of the layer.
+    Add,
+    /// Remove every word of the layer.
+    Remove,
+}
+
+/// A dictionary that is an ordered stack of dictionaries queried as one.
+/// 
+/// Any [Lookup](../trait.Lookup.html) can be a layer. To share one base dictionary among many
+/// layered dictionaries, wrap it in `Arc`.
+/// ```
+/// use std::sync::Arc;
+/// use tokenizer::{Tokenizer, th};
+/// use tokenizer::dict::{Dict, LayeredDict, SizedDict};
+/// 
+/// fn dict(words: &[&str]) -> SizedDict {
+///     let mut dict = Dict::new();
+///     words.iter().for_each(|w| dict.add(w));
+///     dict.into()
+/// }
+/// 
+/// let base = Arc::new(dict(&["ยา", "พารา", "กิน", "ตา", "ตาก", "แดด"]));
+/// let layered = LayeredDict::new(Arc::clone(&base))
+///     .with_additions(dict(&["ยาพารา"]))
+///     .with_removals(dict(&["ตาก"]));
+/// let tokenizer = th::Tokenizer::with_dict(layered);
+/// assert_eq!(tokenizer.tokenize("กินยาพาราตากแดด"), vec!["กิน", "ยาพารา", "ตา", "ก", "แดด"]);
+/// ```
+pub struct LayeredDict {
+    layers: Vec<(LayerKind, Box<dyn Lookup>)>,
+}
+
+impl LayeredDict {
+    /// Construct a layered dictionary with given base dictionary as the bottom layer.
+    pub fn new<D: Lookup + 'static>(base: D) -> LayeredDict {
+        LayeredDict {
+            layers: vec![(LayerKind::Add, Box::new(base))]
+        }
+    }
+
+    /// Put a layer on top of this dictionary.
+    pub fn with_layer<D: Lookup + 'static>(mut self, kind: LayerKind, dict: D) -> LayeredDict {
+        self.push(kind, dict);
+        self
+    }
+
+    /// Put a layer that add every word of `dict` on top of this dictionary.
+    pub fn with_additions<D: Lookup + 'static>(self, dict: D) -> LayeredDict {
+        self.with_layer(LayerKind::Add, dict)
+    }
+
+    /// Put a layer that remove every word of `dict` on top of this dictionary.
+    pub fn with_removals<D: Lookup + 'static>(self, dict: D) -> LayeredDict {
+        self.with_layer(LayerKind::Remove, dict)
+    }
+
+    /// Put a layer on top of this dictionary.
+    pub fn push<D: Lookup + 'static>(&mut self, kind: LayerKind, dict: D) {
+        self.layers.push((kind, Box::new(dict)));
+    }
+
+    /// Number of layers including the base dictionary.
+    pub fn layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Append words found by `lookup` on every layer to `results` sorted by `end_of` each word.
+    /// 
+    /// Layers are looked up from top to bottom directly into `results` so the top most layer that
+    /// has a word decide whether it is kept. Words removed by a layer stay after the kept words until
+    /// every layer is looked up so no other buffer is needed.
+    fn merge_layers<T, E, L>(&self, results: &mut Vec<T>, end_of: E, lookup: L)
+    where E: Fn(&T) -> usize, L: Fn(&dyn Lookup, &mut Vec<T>) {
+        let start = results.len();
+        // results[start..kept] are kept words and results[kept..] are removed words
+        let mut kept = start;
+
+        for (kind, dict) in self.layers.iter().rev() {
+            let decided = results.len();
+            lookup(dict.as_ref(), results);
+
+            // Move words that no layer above has decided to the front of new words
+            let mut undecided = decided;
+            for i in decided..results.len() {
+                let end = end_of(&results[i]);
+
+                if !results[start..decided].iter().any(|word| end_of(word) == end) {
+                    results.swap(undecided, i);
+                    undecided += 1;
+                }
+            }
+            results.truncate(undecided);
+
+            if *kind == LayerKind::Add {
+                results[kept..].rotate_right(undecided - decided);
+                kept += undecided - decided;
+            }
+        }
+
+        results.truncate(kept);
+        results[start..].sort_unstable_by_key(end_of);
+    }
+}
+
+impl std::fmt::Debug for LayeredDict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LayeredDict").field("layers", &self.layers.iter().map(|(kind, _)| kind).collect::<Vec<_>>()).finish()
+    }
+}
+
+impl Lookup for LayeredDict {
+    fn terminals_prefix(&self, value: &str, offset: usize, results: &mut Vec<usize>) {
+        self.merge_layers(results, |end| *end, |dict, results| dict.terminals_prefix(value, offset, results));
+    }
+
+    /// A word take its frequency from the top most addition layer that has it.
+    fn frequencies_prefix(&self, value: &str, offset: usize, results: &mut Vec<(usize, u64)>) {
+        self.merge_layers(results, |(end, _)| *end, |dict, results| dict.frequencies_prefix(value, offset, results));
+    }
+
+    /// Sum of total frequency of every addition layer.
+    /// 
+    /// It is an estimation. Layers can't list their words so a word that is in many layers is
+    /// counted many times and a removed word is still counted.
+    fn total_frequency(&self) -> u64 {
+        self.layers.iter().filter(|(kind, _)| *kind == LayerKind::Add).map(|(_, dict)| dict.total_frequency()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests;
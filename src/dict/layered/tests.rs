@@ -0,0 +1,63 @@
+use super::*;
+use crate::dict::{Dict, DoubleArray, SizedDict, WordInfo};
+use crate::dict::tests::dict;
+use std::sync::Arc;
+
+fn terminals<'a, D: Lookup>(dict: &D, value: &'a str) -> Vec<&'a str> {
+    let mut results = Vec::new();
+    dict.terminals_prefix(value, 0, &mut results);
+    results.into_iter().map(|end| &value[..end]).collect()
+}
+
+#[test]
+fn test_layers() {
+    let base = Arc::new(dict(&["กร", "กรร", "กรรม", "กรรมการ"]));
+    let layered = LayeredDict::new(Arc::clone(&base))
+        .with_additions(DoubleArray::from_words(["กรรมก", "กรรมกร"]))
+        .with_removals(dict(&["กรร", "กรรมการ", "กรรมกร"]))
+        .with_additions(dict(&["กรรมการ"]));
+
+    assert_eq!(layered.layers(), 4);
+    assert_eq!(terminals(&*base, "กรรมการ"), vec!["กร", "กรร", "กรรม", "กรรมการ"]);
+    // Removed word can be added back by later layer
+    assert_eq!(terminals(&layered, "กรรมการ"), vec!["กร", "กรรม", "กรรมก", "กรรมการ"]);
+    assert_eq!(terminals(&layered, "กรรมกร"), vec!["กร", "กรรม", "กรรมก"]);
+    assert_eq!(layered.total_frequency(), 4 + 2 + 1);
+}
+
+#[test]
+fn test_frequencies() {
+    let mut base: Dict<WordInfo> = Dict::default();
+    base.insert("กรรม", WordInfo {frequency: 10, tags: vec![]});
+    base.insert("กรรมการ", WordInfo {frequency: 5, tags: vec![]});
+    let mut additions: Dict<WordInfo> = Dict::default();
+    additions.insert("กรรมการ", WordInfo {frequency: 50, tags: vec![]});
+
+    let layered = LayeredDict::new(SizedDict::from(base)).with_layer(LayerKind::Add, SizedDict::from(additions));
+    let mut results = Vec::new();
+    layered.frequencies_prefix("กรรมการ", 0, &mut results);
+    assert_eq!(results, vec![(12, 10), (21, 50)]);
+
+    // Words are appended after existing results without touching them
+    let layered = layered.with_removals(dict(&["กรรม", "กรรมการ"])).with_additions(dict(&["กรรมการ"]));
+    layered.frequencies_prefix("กรรมการ", 0, &mut results);
+    assert_eq!(results, vec![(12, 10), (21, 50), (21, 1)]);
+    let mut results = vec![99];
+    layered.terminals_prefix("กรรมการ", 0, &mut results);
+    assert_eq!(results, vec![99, 21]);
+}
+
+#[test]
+fn test_tokenizer() {
+    use crate::{Tokenizer, th};
+
+    let base = Arc::new(dict(&["ยา", "พารา", "กิน", "ตา", "ตาก", "แดด"]));
+    let tokenizer = th::Tokenizer::with_dict(Arc::clone(&base));
+    assert_eq!(tokenizer.tokenize("กินยาพาราตากแดด"), vec!["กิน", "ยา", "พารา", "ตาก", "แดด"]);
+
+    let layered = LayeredDict::new(Arc::clone(&base))
+        .with_additions(dict(&["ยาพารา"]))
+        .with_removals(dict(&["ตาก"]));
+    let tokenizer = th::Tokenizer::with_dict(layered);
+    assert_eq!(tokenizer.tokenize("กินยาพาราตากแดด"), vec!["กิน", "ยาพารา", "ตา", "ก", "แดด"]);
+}
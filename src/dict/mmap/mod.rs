@@ -0,0 +1,190 @@
+//! Zero-copy dictionary on top of [binary](../binary/index.html) format.
+//! 
+//! [SizedDict](../struct.SizedDict.html) own every node value and every childs on heap.
+//! A large dictionary become a big tree of pointers that each process need to build.
+//! 
+//! [DictView](struct.DictView.html) query a binary dictionary directly from bytes without
+//! deserializing it. With `mmap` feature, [MmapDict](struct.MmapDict.html) memory-map a binary
+//! dictionary file so many processes can share one read-only dictionary through page cache.
+
+use super::Lookup;
+use super::binary::{validate_nodes, Header, RawNode};
+
+/// A read-only dictionary that directly query a binary dictionary in given bytes.
+/// 
+/// The bytes can be made by [SizedDict::to_bytes](../struct.SizedDict.html#method.to_bytes).
+/// It never copy any part of the bytes. Every lookup read node directly from the bytes.
+#[derive(Clone, Copy, Debug)]
+pub struct DictView<'a> {
+    bytes: &'a [u8],
+    /// Number of root nodes
+    roots: usize,
+    /// Offset of value pool in bytes
+    pool_start: usize,
+    /// Size of each node in bytes
+    node_len: usize,
+    /// Number of words in dictionary
+    entries: usize,
+    /// Sum of frequency of every word
+    total_frequency: u64,
+}
+
+impl<'a> DictView<'a> {
+    /// Make a view of binary dictionary in given bytes.
+    /// 
+    /// It validate the header, checksum and every node once so lookup never need to check it again.
+    /// It return an error of kind `InvalidData` if the bytes is not a valid binary dictionary.
+    pub fn new(bytes: &'a [u8]) -> std::io::Result<DictView<'a>> {
+        let header = Header::parse(bytes)?;
+        let (entries, total_frequency) = validate_nodes(bytes, &header)?;
+
+        Ok(DictView {
+            bytes,
+            roots: header.roots,
+            pool_start: header.pool_start(),
+            node_len: header.node_len,
+            entries: entries as usize,
+            total_frequency
+        })
+    }
+
+    /// Number of words in this dictionary.
+    pub fn len(&self) -> usize {
+        self.entries
+    }
+
+    /// Return true if there's no word in this dictionary.
+    pub fn is_empty(&self) -> bool {
+        self.entries == 0
+    }
+
+    /// Get a value of node as bytes.
+    #[inline(always)]
+    fn text(&self, node: &RawNode) -> &'a [u8] {
+        let start = self.pool_start + node.text_start;
+        &self.bytes[start..start + node.text_len]
+    }
+}
+
+impl DictView<'_> {
+    /// Call `f` with an offset of the end and frequency of every word that is a prefix of `&value[offset..]`.
+    /// 
+    /// It has the same traversal strategy as [terminals_prefix](../fn.terminals_prefix.html)
+    /// so it always give the same result as [SizedDict](../struct.SizedDict.html) that was saved into the bytes.
+    fn visit_terminals<F>(&self, value: &str, offset: usize, mut f: F) where F: FnMut(usize, u64) {
+        let value = value.as_bytes();
+        // A queue of range of sibling nodes and an offset of value to be evaluate.
+        let mut eval_queue = std::collections::VecDeque::new();
+        eval_queue.push_back((0, self.roots, offset));
+
+        while let Some((first, count, offset)) = eval_queue.pop_front() {
+            let remain = &value[offset..];
+
+            for index in first..first + count {
+                let node = RawNode::read(self.bytes, self.node_len, index);
+                let text = self.text(&node);
+
+                if remain.starts_with(text) {
+                    // Node value is valid UTF-8 so new offset is always at char boundary
+                    let new_offset = offset + text.len();
+
+                    if node.terminal {
+                        f(new_offset, node.frequency);
+                    }
+
+                    if node.child_count > 0 && new_offset < value.len() {
+                        eval_queue.push_back((node.first_child, node.child_count, new_offset));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Lookup for DictView<'_> {
+    fn terminals_prefix(&self, value: &str, offset: usize, results: &mut Vec<usize>) {
+        self.visit_terminals(value, offset, |end, _| results.push(end))
+    }
+
+    /// Every word of version 1 binary dictionary has frequency 1.
+    fn frequencies_prefix(&self, value: &str, offset: usize, results: &mut Vec<(usize, u64)>) {
+        self.visit_terminals(value, offset, |end, frequency| results.push((end, frequency)))
+    }
+
+    fn total_frequency(&self) -> u64 {
+        self.total_frequency
+    }
+}
+
+/// A memory-mapped binary dictionary.
+/// 
+/// Every process that open the same file share the same physical memory through page cache.
+/// Nothing is deserialized. Every lookup read node directly from mapped memory.
+#[cfg(feature="mmap")]
+pub struct MmapDict {
+    mmap: memmap2::Mmap,
+    roots: usize,
+    pool_start: usize,
+    node_len: usize,
+    entries: usize,
+    total_frequency: u64,
+}
+
+#[cfg(feature="mmap")]
+impl MmapDict {
+    /// Memory-map a binary dictionary file.
+    /// 
+    /// The file shall not be modified while it is mapped. Modifying the file may cause
+    /// incorrect lookup result or crash. To update dictionary, write a new file and rename
+    /// it over the old one instead of writing in place.
+    /// 
+    /// It return an error with the file name if the file cannot be mapped. The error is of kind
+    /// `InvalidData` if the file is not a valid binary dictionary.
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> crate::Result<MmapDict> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path).map_err(|e| crate::Error::io(path, e))?;
+        // Safety: The mapping is read-only. The caller is told not to modify the file while
+        // it is mapped. Every node is validated by `DictView::new` before any lookup.
+        let mmap = unsafe { memmap2::Mmap::map(&file) }.map_err(|e| crate::Error::io(path, e))?;
+        let DictView { roots, pool_start, node_len, entries, total_frequency, .. } = DictView::new(&mmap).map_err(|e| crate::Error::io(path, e))?;
+
+        Ok(MmapDict {
+            mmap,
+            roots,
+            pool_start,
+            node_len,
+            entries,
+            total_frequency
+        })
+    }
+
+    /// Get a [DictView](struct.DictView.html) of mapped dictionary.
+    pub fn view(&self) -> DictView<'_> {
+        DictView {
+            bytes: &self.mmap,
+            roots: self.roots,
+            pool_start: self.pool_start,
+            node_len: self.node_len,
+            entries: self.entries,
+            total_frequency: self.total_frequency
+        }
+    }
+}
+
+#[cfg(feature="mmap")]
+impl Lookup for MmapDict {
+    fn terminals_prefix(&self, value: &str, offset: usize, results: &mut Vec<usize>) {
+        self.view().terminals_prefix(value, offset, results)
+    }
+
+    fn frequencies_prefix(&self, value: &str, offset: usize, results: &mut Vec<(usize, u64)>) {
+        self.view().frequencies_prefix(value, offset, results)
+    }
+
+    fn total_frequency(&self) -> u64 {
+        self.view().total_frequency()
+    }
+}
+
+#[cfg(test)]
+mod tests;
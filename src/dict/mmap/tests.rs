@@ -0,0 +1,69 @@
+use super::*;
+use crate::dict::{Dict, SizedDict};
+
+/// Assert that given dictionary give the same result as `expected` on every offset of every text.
+fn assert_same_lookup<D: Lookup>(expected: &SizedDict, actual: &D) {
+    let texts = [
+        "กรรมการบ้านเอาการเอางาน",
+        "อาจารย์กรณ์การละเล่นการบูรณ์",
+        "xyzกรรมกรabc",
+        "",
+    ];
+    let mut expected_results = Vec::new();
+    let mut actual_results = Vec::new();
+
+    for text in texts.iter() {
+        for (offset, _) in text.char_indices() {
+            expected_results.clear();
+            actual_results.clear();
+            expected.terminals_prefix(text, offset, &mut expected_results);
+            actual.terminals_prefix(text, offset, &mut actual_results);
+            assert_eq!(expected_results, actual_results, "Lookup mismatch on {} at {}", text, offset);
+        }
+    }
+}
+
+#[test]
+fn test_view_consistent_with_sized_dict() {
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    let bytes = dict.to_bytes();
+    let view = DictView::new(&bytes).unwrap();
+
+    assert_eq!(view.len(), 12);
+    assert_same_lookup(&dict, &view);
+}
+
+#[test]
+fn test_view_invalid_bytes() {
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    let bytes = dict.to_bytes();
+
+    assert!(DictView::new(&bytes[1..]).is_err());
+    assert!(DictView::new(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn test_view_tokenizer() {
+    use crate::tokenizer::Tokenizer;
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    let bytes = dict.to_bytes();
+    let tokenizer = crate::th::Tokenizer::with_dict(DictView::new(&bytes).unwrap());
+
+    assert_eq!(tokenizer.tokenize("เอากรรมกรที่เอาการเอางาน"), vec!["เอา", "กรรมกร", "ที่", "เอาการเอางาน"]);
+}
+
+#[cfg(feature="mmap")]
+#[test]
+fn test_mmap_dict() {
+    let path = std::env::temp_dir().join(format!("tokenizer_test_mmap_dict_{}.bin", std::process::id()));
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    dict.save(&path).unwrap();
+    let mapped = MmapDict::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(mapped.view().len(), 12);
+    assert_same_lookup(&dict, &mapped);
+
+    let path = std::path::Path::new("data/th.txt");
+    assert!(matches!(MmapDict::open(path), Err(crate::Error::Io {path: Some(p), ..}) if p == path));
+}
@@ -3,10 +3,24 @@
 //! The most important part is
 //! - [Dict](struct.Dict.html) - A mutable dictionary for composing a dictionary by using `Node`.
 //! - [SizedDict](struct.SizedDict.html) - A finalized dictionary which shall not be modified.
+//!   It can tell whether a word is in dictionary, list words and find words by prefix.
+//!   Both of them can carry a payload on each word, e.g. [WordInfo](struct.WordInfo.html) loaded from TSV file.
 //! - [Node](struct.Node.html) - A mutable Node. It is essential part of dictionary.
 //! - [SizedNode](struct.SizedNode.html) - A finalized node. It shall not be modified.
+//! - [SizedDict::to_bytes](struct.SizedDict.html#method.to_bytes) - A binary format to save and load [SizedDict](struct.SizedDict.html).
 //! - [terminals_prefix](fn.terminals_prefix.html) - A function that take slice of 
-//! [SizedNode](struct.SizedNode.html) and attempt to find all possible matched entries.
+//!   [SizedNode](struct.SizedNode.html) and attempt to find all possible matched entries.
+//! - [Lookup](trait.Lookup.html) - A trait of read-only dictionary that tokenizer use to find words.
+//! - [DoubleArray](struct.DoubleArray.html) - A double-array trie dictionary. It is faster to lookup than `SizedDict`.
+//! - [DictView](struct.DictView.html) - A zero-copy view of binary dictionary. It can be memory-mapped
+//!   by [MmapDict](struct.MmapDict.html) with `mmap` feature.
+//! - [LayeredDict](struct.LayeredDict.html) - A stack of dictionaries that add or remove words, queried as one.
+//! - [LoadPolicy](enum.LoadPolicy.html) - How to handle bad entries when loading dictionary from text file.
+//! - [compile_txt](fn.compile_txt.html) - Compile a word list in a build script so it can be embedded by
+//!   [include_dict](../macro.include_dict.html) without any construction at runtime.
+//! - [bundled](fn.bundled.html) - A small dictionary of common Thai words embedded by `bundled-dict` feature.
+
+use crate::EntryError;
 
 /// Find a node that has longest common prefix matched with given value.
 /// It return index of the node and the length of the matched.
@@ -32,7 +46,7 @@
 /// - (3, 1) - The third node has one character common prefix with given value.
 /// 
 /// The index will always `< nodes.len()` and the length will always `<=` node value
-fn find_longest_prefix(nodes: &[Node], value: &str) -> (usize, usize) {
+fn find_longest_prefix<V>(nodes: &[Node<V>], value: &str) -> (usize, usize) {
     let mut index = nodes.len();
     let mut longest = 0;
     let value_first_char = value.chars().next();
@@ -74,9 +88,9 @@ fn find_longest_prefix(nodes: &[Node], value: &str) -> (usize, usize) {
 /// It will work recursively until first node with > 1 or leaf node reach.
 /// 
 /// It is unlikely to happen if entire tree is construct via `add` or `load` method.
-#[allow(unused)]
-fn try_merge(node: &mut Node) {
-    if node.terminal {
+/// It happen when a word is removed via `remove` method.
+fn try_merge<V>(node: &mut Node<V>) {
+    if node.terminal.is_some() {
         // Cannot collapse terminal node
         return
     }
@@ -94,18 +108,22 @@ fn try_merge(node: &mut Node) {
     let mut childs = node.childs.take().unwrap();
     try_merge(&mut childs[0]); // traverse until either hit leaf node or found a node with multiple child
     node.value.push_str(&childs[0].value);
-    node.terminal = childs[0].terminal; // node type shall be propagate back to parent when collapsed
+    node.terminal = childs[0].terminal.take(); // node type shall be propagate back to parent when collapsed
     node.childs = childs[0].childs.take();
 }
 
 /// Add value to given nodes while maintaining the ascending order of nodes.
 /// It's always succeed.
-fn add_node(nodes: &mut Vec<Node>, value: String) {
+/// 
+/// The payload is attached to the node that represent the value. If the value
+/// is already in the nodes, the payload replace the old one and the old one is returned.
+fn add_node<V>(nodes: &mut Vec<Node<V>>, value: String, payload: V) -> Option<V> {
     let (i, len) = find_longest_prefix(&*nodes, &value);
 
     if len == 0 {
         // new node at current level
-        nodes.insert(i, Node {childs: Some(vec![]), terminal: true, value: value});
+        nodes.insert(i, Node {childs: Some(vec![]), terminal: Some(payload), value});
+        None
     } else {
         // Four possibilities here.
         // 1. Node is prefix of given value
@@ -119,12 +137,12 @@ fn add_node(nodes: &mut Vec<Node>, value: String) {
             // 100 % match with node value
             if len == value_len {
                 // 100% match on both node_value and given value
-                nodes[i].terminal = true;
+                nodes[i].terminal.replace(payload)
             } else {
                 // Node is prefix of given value as it is impossible to have len > value
 
                 // add remain of value as child of current node
-                add_node(nodes[i].childs.as_mut().unwrap(), value[len..].to_owned());
+                add_node(nodes[i].childs.as_mut().unwrap(), value[len..].to_owned(), payload)
             }
         } else {
             // Prefix of node value match as it is impossible to have length > node_len
@@ -136,11 +154,12 @@ fn add_node(nodes: &mut Vec<Node>, value: String) {
                 let child_of_childs = nodes[i].childs.take(); // move all childs out of current node
                 let child = Node { // create new child to represent current node value
                     childs: child_of_childs, // move all childs back to restore represent current node's childs
-                    terminal: nodes[i].terminal, // it shall have similar node type to original of it type
+                    terminal: nodes[i].terminal.take(), // it shall have similar node type to original of it type
                     value: remain
                 }; 
                 nodes[i].childs = Some(vec![child]); // add a represent of current node as child of given value
-                nodes[i].terminal = true; // since node value is equal to given value, it's terminal node
+                nodes[i].terminal = Some(payload); // since node value is equal to given value, it's terminal node
+                None
             } else {
                 // there's a common prefix on both node value and given value.
                 let node_remain = nodes[i].value[len..].to_owned(); // remain of node value
@@ -149,56 +168,392 @@ fn add_node(nodes: &mut Vec<Node>, value: String) {
                 let child_of_childs = nodes[i].childs.take(); // move all childs out of current node
                 let child = Node { // create new child to represent current node value
                     childs: child_of_childs, // move all childs back to restore represent current node's childs
-                    terminal: nodes[i].terminal, // it shall have similar node type to original of it type
+                    terminal: nodes[i].terminal.take(), // it shall have similar node type to original of it type
                     value: node_remain
                 };
                 let mut childs = vec![child]; // construct sibling to be re-attached to current node
-                add_node(&mut childs, value_remain); // add remain value as sibling of remain of current node
+                add_node(&mut childs, value_remain, payload); // add remain value as sibling of remain of current node
                 nodes[i].childs = Some(childs); // reconnect all childs back
-                nodes[i].terminal = false // It is no longer terminal as it is just a prefix of two nodes
+                // It is no longer terminal as it is just a prefix of two nodes. It was already taken above.
+                None
             }
         }
     }
 }
 
+/// Remove value from given nodes while maintaining the ascending order of nodes.
+/// It return true if value was a word in given nodes.
+/// 
+/// After the value is removed, a node that is no longer terminal and has no child is removed.
+/// A node that is no longer terminal and has only one child is merged with its child.
+fn remove_node<V>(nodes: &mut Vec<Node<V>>, value: &str) -> bool {
+    let (i, len) = find_longest_prefix(&*nodes, value);
+
+    if len == 0 || len != nodes[i].value.len() {
+        // Node value is not a prefix of given value so the value cannot be in this branch.
+        return false
+    }
+
+    if len == value.len() {
+        // 100% match on both node value and given value
+        if nodes[i].terminal.take().is_none() {
+            return false
+        }
+    } else if !nodes[i].childs.as_mut().is_some_and(|childs| remove_node(childs, &value[len..])) {
+        // Remain of the value is not in any child of this node
+        return false
+    }
+
+    let node = &mut nodes[i];
+
+    if node.terminal.is_none() {
+        match node.childs.as_ref().map_or(0, |childs| childs.len()) {
+            0 => {nodes.remove(i);}, // Node is no longer needed
+            1 => try_merge(node), // Node is just a prefix of its only child
+            _ => {} // Node is still a common prefix of many childs
+        }
+    }
+
+    true
+}
+
+/// Information of a word loaded from TSV dictionary by [load_tsv](struct.Dict.html#method.load_tsv).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WordInfo {
+    /// Number of occurence of the word in corpus.
+    pub frequency: u64,
+    /// Tags of the word, e.g. part-of-speech.
+    pub tags: Vec<String>,
+}
+
+/// A payload that can tell how often a word occur.
+/// 
+/// It is used by [Lookup](trait.Lookup.html) of [SizedDict](struct.SizedDict.html) to weigh each word.
+pub trait Frequency {
+    /// Number of occurence of the word.
+    fn frequency(&self) -> u64;
+}
+
+/// A word without payload is treated as if it occur once.
+impl Frequency for () {
+    fn frequency(&self) -> u64 {
+        1
+    }
+}
+
+impl Frequency for u64 {
+    fn frequency(&self) -> u64 {
+        *self
+    }
+}
+
+impl Frequency for WordInfo {
+    fn frequency(&self) -> u64 {
+        self.frequency
+    }
+}
+
+/// A payload that can be made from frequency of a word.
+/// 
+/// It let a dictionary file be loaded into a dictionary of any such payload, e.g. by
+/// [ReloadableTokenizer](/tokenizer/th/struct.ReloadableTokenizer.html).
+pub trait FromFrequency: Frequency {
+    /// Make a payload of a word that occur `frequency` times.
+    fn from_frequency(frequency: u64) -> Self;
+}
+
+/// The frequency is dropped.
+impl FromFrequency for () {
+    fn from_frequency(_: u64) {}
+}
+
+impl FromFrequency for u64 {
+    fn from_frequency(frequency: u64) -> u64 {
+        frequency
+    }
+}
+
+/// The word has no tag.
+impl FromFrequency for WordInfo {
+    fn from_frequency(frequency: u64) -> WordInfo {
+        WordInfo {frequency, tags: vec![]}
+    }
+}
+
 /// A mutable dictionary dictionary.
 /// It is used as root of many childs [Node](struct.Node.html).
+/// 
+/// Once all words are added, convert it into [SizedDict](struct.SizedDict.html) to lookup for words.
+/// 
+/// Each word may carry a payload of type `V`, e.g. [WordInfo](struct.WordInfo.html).
+/// By default, there's no payload.
 #[derive(Debug, PartialEq)]
-pub(crate) struct Dict {
-    root: Vec<Node>
+pub struct Dict<V = ()> {
+    root: Vec<Node<V>>
 }
 
-impl Dict {
-    /// Create new empty dictionary
-    pub fn new() -> Dict {
+impl<V> Default for Dict<V> {
+    fn default() -> Dict<V> {
         Dict {
             root: Vec::new()
         }
     }
+}
+
+impl Dict {
+    /// Create new empty dictionary
+    pub fn new() -> Dict {
+        Dict::default()
+    }
+
+    /// Load dictionary from text file. Each line is a word.
+    /// 
+    /// It use [LoadPolicy::Lenient](enum.LoadPolicy.html#variant.Lenient). Use
+    /// [load_txt_with](struct.Dict.html#method.load_txt_with) to choose other policy.
+    pub fn load_txt<P: AsRef<std::path::Path>>(txt_file: P) -> crate::Result<Dict> {
+        Dict::load_txt_with(txt_file, LoadPolicy::Lenient).map(|(dict, _)| dict)
+    }
+
+    /// Load dictionary from text file using given policy to handle bad entries.
+    /// 
+    /// It return the dictionary along with every skipped entry. Only
+    /// [LoadPolicy::SkipAndReport](enum.LoadPolicy.html#variant.SkipAndReport) report skipped entry.
+    /// ```no_run
+    /// use tokenizer::dict::{Dict, LoadPolicy};
+    /// let (dict, skipped) = Dict::load_txt_with("path/to/dictionary.txt", LoadPolicy::SkipAndReport)?;
+    /// for error in skipped {
+    ///     eprintln!("{}", error); // e.g. path/to/dictionary.txt:12: duplicate word "ภาษา"
+    /// }
+    /// # Ok::<(), tokenizer::Error>(())
+    /// ```
+    pub fn load_txt_with<P: AsRef<std::path::Path>>(txt_file: P, policy: LoadPolicy) -> crate::Result<(Dict, Vec<crate::Error>)> {
+        load_entries(txt_file.as_ref(), policy, false, |line| Ok((line, ())), |_, _| {})
+    }
+}
+
+impl Dict<WordInfo> {
+    /// Load dictionary from tab separated file.
+    /// 
+    /// Each line is a word, its frequency and its comma separated tags, e.g. `กรรม\t120\tNOUN,VERB`.
+    /// The frequency and tags columns are optional. 
+    /// 
+    /// It use [LoadPolicy::Lenient](enum.LoadPolicy.html#variant.Lenient) so an empty line or a line with
+    /// invalid frequency is ignored. If the same word appear more than once, the frequencies are summed
+    /// and the tags are appended. Use [load_tsv_with](struct.Dict.html#method.load_tsv_with) to choose other policy.
+    pub fn load_tsv<P: AsRef<std::path::Path>>(tsv_file: P) -> crate::Result<Dict<WordInfo>> {
+        Dict::load_tsv_with(tsv_file, LoadPolicy::Lenient).map(|(dict, _)| dict)
+    }
+
+    /// Load dictionary from tab separated file using given policy to handle bad entries.
+    /// 
+    /// It return the dictionary along with every skipped entry. A frequency that is not an unsigned
+    /// integer is [EntryError::InvalidFrequency](/tokenizer/enum.EntryError.html#variant.InvalidFrequency).
+    pub fn load_tsv_with<P: AsRef<std::path::Path>>(tsv_file: P, policy: LoadPolicy) -> crate::Result<(Dict<WordInfo>, Vec<crate::Error>)> {
+        load_entries(tsv_file.as_ref(), policy, false, |line| {
+            let mut columns = line.split('\t');
+            let word = columns.next().unwrap_or_default();
+            let frequency = match columns.next().map(str::trim) {
+                Some(freq) if !freq.is_empty() => freq.parse().map_err(|_| EntryError::InvalidFrequency(freq.to_owned()))?,
+                _ => 0
+            };
+            let tags = columns.next().map_or(vec![], |tags| {
+                tags.split(',').map(str::trim).filter(|t| !t.is_empty()).map(str::to_owned).collect()
+            });
+
+            Ok((word, WordInfo {frequency, tags}))
+        }, |info, duplicate| {
+            info.frequency += duplicate.frequency;
+            info.tags.extend(duplicate.tags);
+        })
+    }
+}
+
+/// How to handle a bad entry when loading dictionary from text file.
+/// 
+/// A bad entry is a line that is not a valid UTF-8, an empty line, a duplicate word or a word that
+/// has white space. For TSV file, a line with invalid frequency is also a bad entry.
+/// See [EntryError](/tokenizer/enum.EntryError.html).
+/// 
+/// Regardless of policy, a byte order mark at the beginning of file and a `\r` at the end of
+/// each line are removed. A line that begin with `#` is a word unless the file is compiled by
+/// [compile_txt](fn.compile_txt.html), where it is a comment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LoadPolicy {
+    /// Stop at the first bad entry and return it as an error.
+    Strict,
+    /// Skip every bad entry and report them along with the dictionary.
+    SkipAndReport,
+    /// Trim white space around each word and merge duplicate words.
+    /// Every other bad entry is silently skipped.
+    #[default]
+    Lenient,
+}
+
+/// Load text dictionary file the same way as [Dict::load_txt](struct.Dict.html#method.load_txt).
+/// Each word carry a payload made from frequency 1, the frequency of a word without payload.
+pub(crate) fn load_txt<V: FromFrequency>(path: &std::path::Path) -> crate::Result<SizedDict<V>> {
+    load_entries(path, LoadPolicy::Lenient, false, |line| Ok((line, V::from_frequency(1))), |_, _| {}).map(|(dict, _)| dict.into())
+}
+
+/// Load binary dictionary file. The payload of each word is made from its frequency.
+pub(crate) fn load_compiled<V: FromFrequency>(path: &std::path::Path) -> crate::Result<SizedDict<V>> {
+    std::fs::read(path).and_then(|bytes| binary::decode(&bytes, V::from_frequency)).map_err(|e| crate::Error::io(path, e))
+}
+
+/// Load entries of text dictionary file according to given policy.
+/// 
+/// Each line without line ending is given to `parse`. It return a word and its payload.
+/// If the word is already in dictionary and the policy is lenient, `merge` is called with
+/// existing payload and new payload. If `comments` is true, a line whose first non white space
+/// char is `#` is skipped as [EntryError::Comment](/tokenizer/enum.EntryError.html#variant.Comment).
+fn load_entries<V, F, M>(path: &std::path::Path, policy: LoadPolicy, comments: bool, parse: F, merge: M) -> crate::Result<(Dict<V>, Vec<crate::Error>)>
+where F: for<'l> Fn(&'l str) -> Result<(&'l str, V), EntryError>, M: Fn(&mut V, V) {
+    use std::io::{BufRead, BufReader};
+
+    let mut reader = BufReader::new(std::fs::File::open(path).map_err(|e| crate::Error::io(path, e))?);
+    let mut dict = Dict::default();
+    let mut skipped = Vec::new();
+    let mut bytes = Vec::new();
+    let mut line = 0;
+
+    loop {
+        bytes.clear();
+
+        if reader.read_until(b'\n', &mut bytes).map_err(|e| crate::Error::io(path, e))? == 0 {
+            break
+        }
+
+        line += 1;
+        let mut entry = bytes.as_slice();
+        entry = entry.strip_suffix(b"\n").unwrap_or(entry);
+        entry = entry.strip_suffix(b"\r").unwrap_or(entry);
 
-    /// Load dictionary from text file
-    pub fn load_txt<P: AsRef<std::path::Path>>(txt_file: P) -> std::io::Result<Dict> {
-        use std::io::{BufRead, BufReader};
-        let reader = BufReader::new(std::fs::File::open(txt_file)?);
-        let mut dict = Dict::new();
-        reader.lines().for_each(|line| {
-            dict.add(line.as_ref().unwrap());
+        if line == 1 {
+            entry = entry.strip_prefix("\u{feff}".as_bytes()).unwrap_or(entry);
+        }
+
+        let result = if comments && entry.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'#') {
+            Err(EntryError::Comment)
+        } else {
+            std::str::from_utf8(entry).map_err(|_| EntryError::InvalidUtf8)
+        }.and_then(&parse).and_then(|(word, payload)| {
+            let word = if policy == LoadPolicy::Lenient {word.trim()} else {word};
+
+            if word.is_empty() {
+                return Err(EntryError::Empty)
+            } else if word.contains(char::is_whitespace) {
+                return Err(EntryError::Whitespace(word.to_owned()))
+            }
+
+            match dict.get_mut(word) {
+                Some(existing) if policy == LoadPolicy::Lenient => merge(existing, payload),
+                Some(_) => return Err(EntryError::Duplicate(word.to_owned())),
+                None => {
+                    dict.insert(word, payload);
+                }
+            }
+
+            Ok(())
         });
-        Ok(dict)
+
+        if let Err(kind) = result {
+            match policy {
+                LoadPolicy::Strict => return Err(crate::Error::entry(path, line, kind)),
+                LoadPolicy::SkipAndReport => skipped.push(crate::Error::entry(path, line, kind)),
+                LoadPolicy::Lenient => ()
+            }
+        }
     }
 
+    Ok((dict, skipped))
+}
+
+impl<V> Dict<V> {
     /// Add new token into dictionary.
     /// The value will be clone and owned by this object.
-    pub fn add(&mut self, value: &str) {
-        add_node(&mut self.root, value.to_owned());
+    /// 
+    /// The token carry a default payload. If the token is already in dictionary,
+    /// its payload is left untouched.
+    pub fn add(&mut self, value: &str) where V: Default {
+        if self.get_mut(value).is_none() {
+            add_node(&mut self.root, value.to_owned(), V::default());
+        }
+    }
+
+    /// Add new token along with its payload into dictionary.
+    /// If the token is already in dictionary, the payload is replaced and
+    /// the old payload is returned.
+    pub fn insert(&mut self, value: &str, payload: V) -> Option<V> {
+        add_node(&mut self.root, value.to_owned(), payload)
+    }
+
+    /// Get a mutable reference to payload of given token.
+    /// It return None if the token is not in dictionary.
+    pub fn get_mut(&mut self, value: &str) -> Option<&mut V> {
+        let mut nodes = &mut self.root;
+        let mut value = value;
+
+        loop {
+            let (i, len) = find_longest_prefix(&*nodes, value);
+
+            if len == 0 || len != nodes[i].value.len() {
+                return None
+            } else if len == value.len() {
+                return nodes[i].terminal.as_mut()
+            }
+
+            value = &value[len..];
+            nodes = nodes[i].childs.as_mut()?;
+        }
+    }
+
+    /// Remove a token from dictionary.
+    /// It return true if the token was in dictionary.
+    /// 
+    /// The dictionary will have the same structure as if the token was never added.
+    pub fn remove(&mut self, value: &str) -> bool {
+        remove_node(&mut self.root, value)
+    }
+}
+
+/// Convert immutable dict back into mutable dict so words can be added or removed.
+impl<V> core::convert::From<SizedDict<V>> for Dict<V> {
+    fn from(dict: SizedDict<V>) -> Dict<V> {
+        fn into_node<V>(node: SizedNode<V>) -> Node<V> {
+            Node {
+                childs: Some(node.childs.into_vec().into_iter().map(into_node).collect()),
+                terminal: node.terminal,
+                value: node.value
+            }
+        }
+
+        Dict {
+            root: dict.root.into_vec().into_iter().map(into_node).collect()
+        }
     }
 }
 
 /// A fixed number of elements dictionary.
 /// 
-/// It let user use method [matcher](struct.SizedDict.html#method.matcher) to
+/// It let user use method [common_prefixes_of](struct.SizedDict.html#method.common_prefixes_of) to
 /// match multiple possible occurences of word in dictionary to a given string.
 /// 
+/// For example:
+/// ```
+/// use tokenizer::dict::{Dict, SizedDict};
+/// let mut dict = Dict::new();
+/// dict.add("กรรม");
+/// dict.add("กรรมการ");
+/// dict.add("การ");
+/// let dict = SizedDict::from(dict);
+/// 
+/// assert!(dict.contains("กรรม"));
+/// assert_eq!(dict.len(), 3);
+/// assert_eq!(dict.iter().collect::<Vec<String>>(), vec!["กรรม", "กรรมการ", "การ"]);
+/// assert_eq!(dict.words_with_prefix("กร").collect::<Vec<String>>(), vec!["กรรม", "กรรมการ"]);
+/// assert_eq!(dict.common_prefixes_of("กรรมการบ้าน"), vec!["กรรม", "กรรมการ"]);
+/// ```
+/// 
 /// The different from [Dict][struct.Dict.html] is that you cannot add
 /// more word into dict.
 /// 
@@ -206,39 +561,192 @@ impl Dict {
 /// it is highly discouraged. The reason is because the dict is represented by sorted 
 /// prefix tree data structure. You must  take extra precaution for effect on each mutation. That is:
 /// 1. The mutation must keep the order of nodes in that layer. Otherwise, it will cause
-/// invalid node traversal.
+///    invalid node traversal.
 /// 1. The mutation will have effect on both upward and downward direction of the tree value.
 ///
 /// It is easier to just create a new dict.
+/// 
+/// Payload of each word can be retrieved by [get](struct.SizedDict.html#method.get) or
+/// along with every matched prefix by [prefix_matches](struct.SizedDict.html#method.prefix_matches).
 #[derive(Debug, PartialEq)]
-pub(crate) struct SizedDict {
-    pub(crate) root: Box<[SizedNode]>
+pub struct SizedDict<V = ()> {
+    pub(crate) root: Box<[SizedNode<V>]>,
+    /// Number of words in this dictionary
+    pub(crate) len: usize,
 }
 
 /// Convert mutable dict into immutable.
-impl core::convert::From<Dict> for SizedDict {
-    fn from(dict: Dict) -> SizedDict {
+impl<V> core::convert::From<Dict<V>> for SizedDict<V> {
+    fn from(dict: Dict<V>) -> SizedDict<V> {
+        fn count_terminals<V>(nodes: &[SizedNode<V>]) -> usize {
+            nodes.iter().map(|n| n.terminal.is_some() as usize + count_terminals(&n.childs)).sum()
+        }
+
+        let root = dict.root.into_iter().map(|n| n.into()).collect::<Vec<SizedNode<V>>>().into_boxed_slice();
         SizedDict {
-            root: dict.root.into_iter().map(|n| n.into()).collect::<Vec<SizedNode>>().into_boxed_slice()
+            len: count_terminals(&root),
+            root
+        }
+    }
+}
+
+impl<V> SizedDict<V> {
+    /// Number of words in this dictionary.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return true if there's no word in this dictionary.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return true if given word is in this dictionary.
+    pub fn contains(&self, word: &str) -> bool {
+        self.get(word).is_some()
+    }
+
+    /// Get a payload of given word.
+    /// It return None if the word is not in this dictionary.
+    pub fn get(&self, word: &str) -> Option<&V> {
+        let mut payload = None;
+        visit_terminals(&self.root, word, 0, |end, p| if end == word.len() {payload = Some(p)});
+        payload
+    }
+
+    /// Find every word in this dictionary that is a prefix of `&value[offset..]`.
+    /// It push an offset of the end of each word along with its payload into `results`.
+    /// 
+    /// The offset is the same as [Lookup::terminals_prefix](trait.Lookup.html#tymethod.terminals_prefix).
+    /// That is the results are sorted by offset so the last one is the longest word.
+    pub fn prefix_matches<'a>(&'a self, value: &str, offset: usize, results: &mut Vec<(usize, &'a V)>) {
+        visit_terminals(&self.root, value, offset, |end, payload| results.push((end, payload)))
+    }
+
+    /// Iterate over every word in this dictionary in sorted order.
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter::new(&self.root, String::new())
+    }
+
+    /// Iterate over every word along with its payload in sorted order.
+    pub(crate) fn entries(&self) -> impl Iterator<Item=(String, &V)> {
+        let mut iter = self.iter();
+        std::iter::from_fn(move || iter.next_entry())
+    }
+
+    /// Iterate over every word that start with given prefix in sorted order.
+    /// If the prefix itself is a word, it will be the first one.
+    pub fn words_with_prefix(&self, prefix: &str) -> Iter<'_, V> {
+        let mut nodes = &*self.root;
+        let mut consumed = 0;
+
+        'descend: while consumed < prefix.len() {
+            let remain = &prefix[consumed..];
+
+            for node in nodes.iter() {
+                if node.value.starts_with(remain) {
+                    // Every word in this node and its childs start with given prefix.
+                    return Iter::new(std::slice::from_ref(node), prefix[..consumed].to_owned());
+                } else if !node.value.is_empty() && remain.starts_with(&*node.value) {
+                    // The node is part of the prefix. Look further on its childs.
+                    consumed += node.value.len();
+                    nodes = &node.childs;
+                    continue 'descend;
+                }
+            }
+
+            // No node match the prefix
+            return Iter::new(&[], String::new());
+        }
+
+        Iter::new(nodes, prefix.to_owned())
+    }
+
+    /// Return every word in this dictionary that is a prefix of given text.
+    /// The words are sorted by its length so the last one is the longest.
+    pub fn common_prefixes_of<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let mut results = Vec::new();
+        terminals_prefix(&self.root, text, 0, &mut results);
+        results.into_iter().map(|end| &text[..end]).collect()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a SizedDict<V> {
+    type Item = String;
+    type IntoIter = Iter<'a, V>;
+
+    fn into_iter(self) -> Iter<'a, V> {
+        self.iter()
+    }
+}
+
+/// An iterator over words of [SizedDict](struct.SizedDict.html) in sorted order.
+/// 
+/// It traverse the tree depth-first. Each node is visited before its childs and
+/// childs are visited in their sorted order.
+pub struct Iter<'a, V = ()> {
+    /// Nodes to be visited along with length of `prefix` before the node.
+    stack: Vec<(&'a SizedNode<V>, usize)>,
+    /// A word up to the last visited node.
+    prefix: String,
+}
+
+impl<'a, V> Iter<'a, V> {
+    /// Make an iterator over given nodes where every word start with given prefix.
+    fn new(nodes: &'a [SizedNode<V>], prefix: String) -> Iter<'a, V> {
+        let len = prefix.len();
+        Iter {
+            // Reverse the order as the last one on stack will be visited first.
+            stack: nodes.iter().rev().map(|n| (n, len)).collect(),
+            prefix
         }
     }
 }
 
+impl<'a, V> Iter<'a, V> {
+    /// Return next word along with its payload.
+    fn next_entry(&mut self) -> Option<(String, &'a V)> {
+        while let Some((node, len)) = self.stack.pop() {
+            self.prefix.truncate(len);
+            self.prefix.push_str(&node.value);
+
+            let len = self.prefix.len();
+            self.stack.extend(node.childs.iter().rev().map(|n| (n, len)));
+
+            if let Some(payload) = &node.terminal {
+                return Some((self.prefix.clone(), payload));
+            }
+        }
+
+        None
+    }
+}
+
+impl<V> Iterator for Iter<'_, V> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.next_entry().map(|(word, _)| word)
+    }
+}
+
 /// A fully mutable node that let user modify any value.
+/// 
+/// A terminal node hold a payload of the word that end at this node.
 #[derive(Debug, PartialEq)]
-struct Node {
-    childs: Option<Vec<Node>>,
-    terminal: bool,
+struct Node<V> {
+    childs: Option<Vec<Node<V>>>,
+    terminal: Option<V>,
     value: String,
 }
 
 /// Convert Node into SizedNode
-impl core::convert::From<Node> for SizedNode {
-    fn from(node: Node) -> SizedNode {
+impl<V> core::convert::From<Node<V>> for SizedNode<V> {
+    fn from(node: Node<V>) -> SizedNode<V> {
         SizedNode {
-            childs: node.childs.unwrap_or(vec![])
+            childs: node.childs.unwrap_or_default()
                                 .into_iter().map(|c| c.into())
-                                .collect::<Vec<SizedNode>>()
+                                .collect::<Vec<SizedNode<V>>>()
                                 .into_boxed_slice(),
             terminal: node.terminal,
             value: node.value
@@ -251,9 +759,9 @@ impl core::convert::From<Node> for SizedNode {
 /// The only different from [Node](struct.Node.html) is that it have
 /// fixed childs. That mean it cannot add, edit, or remove a child node.
 #[derive(Debug, PartialEq)]
-pub(crate) struct SizedNode {
-    childs: Box<[SizedNode]>,
-    terminal: bool,
+pub(crate) struct SizedNode<V = ()> {
+    childs: Box<[SizedNode<V>]>,
+    terminal: Option<V>,
     value: String,
 }
 
@@ -264,7 +772,7 @@ pub(crate) struct SizedNode {
 /// - `nodes` - A slice of nodes to attempt to check if it is prefix of value
 /// - `value` - A `&str` to find a prefix.
 #[inline(always)]
-fn childs_matched<'a, 'b>(nodes: &'a [SizedNode], value: &'b str) -> Vec<(&'a SizedNode, &'b str)> {
+fn childs_matched<'a, 'b, V>(nodes: &'a [SizedNode<V>], value: &'b str) -> Vec<(&'a SizedNode<V>, &'b str)> {
     nodes.iter().filter_map(|n| 
         if value.starts_with(&*n.value) {
             Some((n, &value[n.value.len()..]))
@@ -285,11 +793,17 @@ fn childs_matched<'a, 'b>(nodes: &'a [SizedNode], value: &'b str) -> Vec<(&'a Si
 /// - `value` - A &str to find a prefix word
 /// - `offset` - Usize of byte value. Caller usually give 0 to find a prefix from start of the text. 
 /// - `results` - A mutable reference to Vec to hold an offset of last character of valid prefixed terminal nodes.
-/// The offset unit is bytes so caller can take a slice using this offset on the string.
+///   The offset unit is bytes so caller can take a slice using this offset on the string.
 /// 
 /// # Return
 /// This function return value in last function parameter. That is `results: &mut Vec<usize>`.
-pub(crate) fn terminals_prefix(nodes: &[SizedNode], value: &str, offset: usize, results: &mut Vec<usize>) {
+pub(crate) fn terminals_prefix<V>(nodes: &[SizedNode<V>], value: &str, offset: usize, results: &mut Vec<usize>) {
+    visit_terminals(nodes, value, offset, |end, _| results.push(end))
+}
+
+/// Call `f` with an offset of the end of each valid word prefix from given value and the payload of that word.
+/// The offsets are given in the same order as [terminals_prefix](fn.terminals_prefix.html).
+fn visit_terminals<'a, V, F>(nodes: &'a [SizedNode<V>], value: &str, offset: usize, mut f: F) where F: FnMut(usize, &'a V) {
     // A queue of pair of nodes and value to be evaluate.
     let mut eval_queue = std::collections::VecDeque::new();
     eval_queue.push_back((nodes, &value[offset..], offset));
@@ -303,12 +817,12 @@ pub(crate) fn terminals_prefix(nodes: &[SizedNode], value: &str, offset: usize,
             // It is more expensive to calculate the offset backward than to simply just store it.
             let new_offset = offset + child.value.len();
             
-            if child.terminal {
+            if let Some(payload) = &child.terminal {
                 // It is terminal node, to results vec
-                results.push(new_offset);
+                f(new_offset, payload);
             }
 
-            if child.childs.len() > 0 && remain.len() > 0 {
+            if !child.childs.is_empty() && !remain.is_empty() {
                 // Put all the childs and their remain to evaluation queue
                 eval_queue.push_back((&*child.childs, remain, new_offset));
             }
@@ -316,5 +830,145 @@ pub(crate) fn terminals_prefix(nodes: &[SizedNode], value: &str, offset: usize,
     }
 }
 
+mod binary;
+mod compile;
+mod double_array;
+mod layered;
+mod mmap;
+
+pub use self::double_array::DoubleArray;
+pub use self::layered::{LayerKind, LayeredDict};
+pub use self::mmap::DictView;
+#[cfg(feature="mmap")]
+pub use self::mmap::MmapDict;
+
+/// Compile given words into binary format without building any [Dict](struct.Dict.html).
+/// 
+/// The words don't need to be sorted. An empty word and a duplicate word are ignored.
+/// The result is the same as [SizedDict::to_bytes](struct.SizedDict.html#method.to_bytes) of the same words.
+/// It can be written to file in a build script then embedded by [include_dict](../macro.include_dict.html).
+pub fn compile_words<S: AsRef<str>>(words: &[S]) -> Vec<u8> {
+    compile::compile(words)
+}
+
+/// Compile a text dictionary file into binary dictionary file, the same format as
+/// [SizedDict::save](struct.SizedDict.html#method.save).
+/// 
+/// The text file is loaded the same way as [Dict::load_txt](struct.Dict.html#method.load_txt) except that
+/// a line whose first non white space char is `#` is a comment. Use [compile_txt_with](fn.compile_txt_with.html)
+/// to choose other [LoadPolicy](enum.LoadPolicy.html).
+/// It is meant to be called from a build script so the binary dictionary can be embedded by
+/// [include_dict](../macro.include_dict.html). For example, in `build.rs`:
+/// ```no_run
+/// let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("words.bin");
+/// tokenizer::dict::compile_txt("words.txt", out)?;
+/// println!("cargo:rerun-if-changed=words.txt");
+/// # Ok::<(), tokenizer::Error>(())
+/// ```
+pub fn compile_txt<P: AsRef<std::path::Path>, Q: AsRef<std::path::Path>>(txt_file: P, bin_file: Q) -> crate::Result<()> {
+    compile_txt_with(txt_file, bin_file, LoadPolicy::Lenient).map(|_| ())
+}
+
+/// Compile a text dictionary file into binary dictionary file using given policy to handle bad entries.
+/// 
+/// It return every skipped entry the same way as [Dict::load_txt_with](struct.Dict.html#method.load_txt_with).
+/// A comment line is skipped as [EntryError::Comment](/tokenizer/enum.EntryError.html#variant.Comment) so
+/// [LoadPolicy::Strict](enum.LoadPolicy.html#variant.Strict) reject a file that has any comment.
+pub fn compile_txt_with<P: AsRef<std::path::Path>, Q: AsRef<std::path::Path>>(txt_file: P, bin_file: Q, policy: LoadPolicy) -> crate::Result<Vec<crate::Error>> {
+    let (dict, skipped) = load_entries(txt_file.as_ref(), policy, true, |line| Ok((line, ())), |_, _| {})?;
+    SizedDict::from(dict).save(bin_file)?;
+    Ok(skipped)
+}
+
+/// Embed a binary dictionary file into the binary and give a [DictView](dict/struct.DictView.html)`<'static>` of it.
+/// 
+/// The file is read by `include_bytes!` at compile time so no dictionary is loaded nor built on heap at runtime.
+/// The bytes are validated once on first use of each call site. It panic if the file is not a valid
+/// binary dictionary. The file can be made in a build script by [compile_txt](dict/fn.compile_txt.html)
+/// or [compile_words](dict/fn.compile_words.html).
+/// ```ignore
+/// use tokenizer::{Tokenizer, th};
+/// let dict = tokenizer::include_dict!(concat!(env!("OUT_DIR"), "/words.bin"));
+/// let tokenizer = th::Tokenizer::with_dict(dict);
+/// ```
+#[macro_export]
+macro_rules! include_dict {
+    ($file:expr) => {{
+        static BYTES: &[u8] = ::core::include_bytes!($file);
+        static VIEW: ::std::sync::OnceLock<$crate::dict::DictView<'static>> = ::std::sync::OnceLock::new();
+
+        *VIEW.get_or_init(|| $crate::dict::DictView::new(BYTES).expect("embedded dictionary is not a valid binary dictionary"))
+    }};
+}
+
+/// A small dictionary of common Thai words that is embedded into the binary by `bundled-dict` feature.
+/// 
+/// The word list is in `data/th_bundled.txt` of this crate. It is compiled into binary format
+/// at build time so it is ready to use without any file or deserialization. It only cover
+/// common words. Use a larger dictionary for better accuracy.
+#[cfg(feature="bundled-dict")]
+pub fn bundled() -> DictView<'static> {
+    include_dict!(concat!(env!("OUT_DIR"), "/th_bundled.bin"))
+}
+
+/// A read-only dictionary that a dictionary based tokenizer can query.
+/// 
+/// There are many ways to represent a dictionary. Each of them shall implement this trait
+/// so tokenizer can use any of them interchangeably.
+pub trait Lookup: Send + Sync {
+    /// Find every word in dictionary that is a prefix of `&value[offset..]` and push an offset
+    /// of the end of each word into `results`. The offset is in bytes and is relative to `value`
+    /// so caller can take a slice `&value[offset..result]` to get the word.
+    /// 
+    /// The pushed offsets are sorted in ascending order. That is the last one is the longest word.
+    fn terminals_prefix(&self, value: &str, offset: usize, results: &mut Vec<usize>);
+
+    /// Same as [terminals_prefix](trait.Lookup.html#tymethod.terminals_prefix) but also push
+    /// a frequency of each word along with its offset.
+    /// 
+    /// By default, every word has frequency 1.
+    fn frequencies_prefix(&self, value: &str, offset: usize, results: &mut Vec<(usize, u64)>) {
+        let mut offsets = Vec::new();
+        self.terminals_prefix(value, offset, &mut offsets);
+        results.extend(offsets.into_iter().map(|end| (end, 1)));
+    }
+
+    /// Sum of frequency of every word in dictionary.
+    fn total_frequency(&self) -> u64;
+}
+
+impl<V: Frequency + Send + Sync> Lookup for SizedDict<V> {
+    fn terminals_prefix(&self, value: &str, offset: usize, results: &mut Vec<usize>) {
+        terminals_prefix(&self.root, value, offset, results)
+    }
+
+    fn frequencies_prefix(&self, value: &str, offset: usize, results: &mut Vec<(usize, u64)>) {
+        visit_terminals(&self.root, value, offset, |end, payload| results.push((end, payload.frequency())))
+    }
+
+    fn total_frequency(&self) -> u64 {
+        fn sum<V: Frequency>(nodes: &[SizedNode<V>]) -> u64 {
+            nodes.iter().map(|n| n.terminal.as_ref().map_or(0, Frequency::frequency) + sum(&n.childs)).sum()
+        }
+
+        sum(&self.root)
+    }
+}
+
+/// A shared dictionary, e.g. a base dictionary of many [LayeredDict](struct.LayeredDict.html).
+impl<D: Lookup + ?Sized> Lookup for std::sync::Arc<D> {
+    fn terminals_prefix(&self, value: &str, offset: usize, results: &mut Vec<usize>) {
+        (**self).terminals_prefix(value, offset, results)
+    }
+
+    fn frequencies_prefix(&self, value: &str, offset: usize, results: &mut Vec<(usize, u64)>) {
+        (**self).frequencies_prefix(value, offset, results)
+    }
+
+    fn total_frequency(&self) -> u64 {
+        (**self).total_frequency()
+    }
+}
+
 #[cfg(test)]
-mod tests;
\ No newline at end of file
+pub(crate) mod tests;
\ No newline at end of file
@@ -1,5 +1,19 @@
 use super::*;
 
+/// A dictionary of given words without payload. It is shared by tests of other modules.
+pub(crate) fn dict(words: &[&str]) -> SizedDict {
+    let mut dict = Dict::new();
+    words.iter().for_each(|word| dict.add(word));
+    dict.into()
+}
+
+/// A dictionary of given words and their frequency. It is shared by tests of other modules.
+pub(crate) fn frequency_dict(words: &[(&str, u64)]) -> SizedDict<u64> {
+    let mut dict = Dict::default();
+    words.iter().for_each(|(word, frequency)| {dict.insert(word, *frequency);});
+    dict.into()
+}
+
 #[test]
 fn test_add_dict() {
     let mut dict = Dict::new();
@@ -18,16 +32,16 @@ fn test_add_dict() {
                     childs: Some(vec![
                         Node {
                             childs: Some(vec![]),
-                            terminal: true,
+                            terminal: Some(()),
                             value: "งาน".to_owned()
                         },
                         Node {
                             childs: Some(vec![]),
-                            terminal: true,
+                            terminal: Some(()),
                             value: "บ้าน".to_owned()
                         },
                     ]),
-                    terminal: false,
+                    terminal: None,
                     value: "การ".to_owned()
                 },
                 Node {
@@ -37,7 +51,7 @@ fn test_add_dict() {
                                 childs: Some(
                                     vec![],
                                 ),
-                                terminal: true,
+                                terminal: Some(()),
                                 value: "ช้าง".to_owned(),
                             },
                             Node {
@@ -47,30 +61,30 @@ fn test_add_dict() {
                                             childs: Some(
                                                 vec![],
                                             ),
-                                            terminal: true,
+                                            terminal: Some(()),
                                             value: "กลุ่ม".to_owned(),
                                         },
                                         Node {
                                             childs: Some(
                                                 vec![],
                                             ),
-                                            terminal: true,
+                                            terminal: Some(()),
                                             value: "บ้าน".to_owned(),
                                         },
                                         Node {
                                             childs: Some(
                                                 vec![],
                                             ),
-                                            terminal: true,
+                                            terminal: Some(()),
                                             value: "เรือน".to_owned(),
                                         },],
                                 ),
-                                terminal: true,
+                                terminal: Some(()),
                                 value: "น".to_owned(),
                             },
                         ],
                     ),
-                    terminal: false,
+                    terminal: None,
                     value: "งา".to_owned(),
                 },
             ]
@@ -89,7 +103,7 @@ fn load_dict() {
                         childs: Some(vec![
                             Node { 
                                 childs: Some(vec![]), 
-                                terminal: true, 
+                                terminal: Some(()), 
                                 value: "ณ์".to_owned()
                             }, Node { 
                                 childs: Some(vec![
@@ -97,80 +111,80 @@ fn load_dict() {
                                         childs: Some(vec![
                                             Node { 
                                                 childs: Some(vec![]), 
-                                                terminal: true, 
+                                                terminal: Some(()), 
                                                 value: "ร".to_owned()
                                             }, 
                                             Node { 
                                                 childs: Some(vec![]), 
-                                                terminal: true, 
+                                                terminal: Some(()), 
                                                 value: "าร".to_owned()
                                             }
                                         ]), 
-                                        terminal: false, 
+                                        terminal: None, 
                                         value: "ก".to_owned()
                                     }
                                 ]), 
-                                terminal: true, 
+                                terminal: Some(()), 
                                 value: "รม".to_owned()
                             }
                         ]), 
-                        terminal: false, 
+                        terminal: None, 
                         value: "ร".to_owned()
                     }, 
                     Node { 
                         childs: Some(vec![
                             Node { 
                                 childs: Some(vec![]), 
-                                terminal: true, 
+                                terminal: Some(()), 
                                 value: "กระจัด".to_owned()
                             }, 
                             Node { 
                                 childs: Some(vec![]), 
-                                terminal: true, 
+                                terminal: Some(()), 
                                 value: "งาน".to_owned()
                             }, 
                             Node { 
                                 childs: Some(vec![
                                     Node { 
                                         childs: Some(vec![]), 
-                                        terminal: true, 
+                                        terminal: Some(()), 
                                         value: "ูรณ์".to_owned()
                                     }, Node { 
                                         childs: Some(vec![]), 
-                                        terminal: true, 
+                                        terminal: Some(()), 
                                         value: "้าน".to_owned()
                                     }
                                 ]), 
-                                terminal: false, 
+                                terminal: None, 
                                 value: "บ".to_owned()
                             }, 
                             Node { 
                                 childs: Some(vec![]), 
-                                terminal: true, 
+                                terminal: Some(()), 
                                 value: "ละเล่น".to_owned()
                             }
                         ]), 
-                        terminal: false, 
+                        terminal: None, 
                         value: "าร".to_owned()
                     }
                 ]), 
-                terminal: false, 
+                terminal: None, 
                 value: "ก".to_owned()
             }, 
             Node { 
                 childs: Some(vec![]), 
-                terminal: true, 
+                terminal: Some(()), 
                 value: "อาจารย์".to_owned()
             }, 
             Node { 
                 childs: Some(vec![
                     Node { 
                         childs: Some(vec![]), 
-                        terminal: true, 
+                        terminal: Some(()), 
                         value: "การเอางาน".to_owned()
                     }
                 ]), 
-                terminal: true, 
+                terminal: Some(()), 
                 value: "เอา".to_owned()
             }
         ]
@@ -193,7 +207,7 @@ fn test_sized_dict() {
                             childs: Box::new([
                                 SizedNode { 
                                     childs: Box::new([]), 
-                                    terminal: true, 
+                                    terminal: Some(()), 
                                     value: "ณ์".to_owned()
                                 }, SizedNode { 
                                     childs: Box::new([
@@ -201,83 +215,372 @@ fn test_sized_dict() {
                                             childs: Box::new([
                                                 SizedNode { 
                                                     childs: Box::new([]), 
-                                                    terminal: true, 
+                                                    terminal: Some(()), 
                                                     value: "ร".to_owned()
                                                 }, 
                                                 SizedNode { 
                                                     childs: Box::new([]), 
-                                                    terminal: true, 
+                                                    terminal: Some(()), 
                                                     value: "าร".to_owned()
                                                 }
                                             ]), 
-                                            terminal: false, 
+                                            terminal: None, 
                                             value: "ก".to_owned()
                                         }
                                     ]), 
-                                    terminal: true, 
+                                    terminal: Some(()), 
                                     value: "รม".to_owned()
                                 }
                             ]), 
-                            terminal: false, 
+                            terminal: None, 
                             value: "ร".to_owned()
                         }, 
                         SizedNode { 
                             childs: Box::new([
                                 SizedNode { 
                                     childs: Box::new([]), 
-                                    terminal: true, 
+                                    terminal: Some(()), 
                                     value: "กระจัด".to_owned()
                                 }, 
                                 SizedNode { 
                                     childs: Box::new([]), 
-                                    terminal: true, 
+                                    terminal: Some(()), 
                                     value: "งาน".to_owned()
                                 }, 
                                 SizedNode { 
                                     childs: Box::new([
                                         SizedNode { 
                                             childs: Box::new([]), 
-                                            terminal: true, 
+                                            terminal: Some(()), 
                                             value: "ูรณ์".to_owned()
                                         }, SizedNode { 
                                             childs: Box::new([]), 
-                                            terminal: true, 
+                                            terminal: Some(()), 
                                             value: "้าน".to_owned()
                                         }
                                     ]), 
-                                    terminal: false, 
+                                    terminal: None, 
                                     value: "บ".to_owned()
                                 }, 
                                 SizedNode { 
                                     childs: Box::new([]), 
-                                    terminal: true, 
+                                    terminal: Some(()), 
                                     value: "ละเล่น".to_owned()
                                 }
                             ]), 
-                            terminal: false, 
+                            terminal: None, 
                             value: "าร".to_owned()
                         }
                     ]), 
-                    terminal: false, 
+                    terminal: None, 
                     value: "ก".to_owned()
                 }, 
                 SizedNode { 
                     childs: Box::new([]), 
-                    terminal: true, 
+                    terminal: Some(()), 
                     value: "อาจารย์".to_owned()
                 }, 
                 SizedNode { 
                     childs: Box::new([
                         SizedNode { 
                             childs: Box::new([]), 
-                            terminal: true, 
+                            terminal: Some(()), 
                             value: "การเอางาน".to_owned()
                         }
                     ]), 
-                    terminal: true, 
+                    terminal: Some(()), 
                     value: "เอา".to_owned()
                 }
-            ])
+            ]),
+            len: 12
         }
     );
-}
\ No newline at end of file
+}
+#[test]
+fn test_sized_dict_api() {
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+
+    assert_eq!(dict.len(), 12);
+    assert!(!dict.is_empty());
+    assert!(dict.contains("กรรมการ"));
+    assert!(dict.contains("เอา"));
+    assert!(!dict.contains("กรร"));
+    assert!(!dict.contains("การ"));
+    assert!(!dict.contains("เอาการ"));
+
+    let mut words: Vec<String> = std::fs::read_to_string("data/th.txt").unwrap().lines().map(|l| l.to_owned()).collect();
+    words.sort();
+    assert_eq!(dict.iter().collect::<Vec<String>>(), words);
+    assert_eq!((&dict).into_iter().count(), 12);
+
+    assert_eq!(dict.words_with_prefix("กรร").collect::<Vec<String>>(), vec!["กรรม", "กรรมกร", "กรรมการ"]);
+    assert_eq!(dict.words_with_prefix("กรรม").collect::<Vec<String>>(), vec!["กรรม", "กรรมกร", "กรรมการ"]);
+    assert_eq!(dict.words_with_prefix("การบ").collect::<Vec<String>>(), vec!["การบูรณ์", "การบ้าน"]);
+    assert_eq!(dict.words_with_prefix("เอาก").collect::<Vec<String>>(), vec!["เอาการเอางาน"]);
+    assert_eq!(dict.words_with_prefix("").count(), 12);
+    assert_eq!(dict.words_with_prefix("ข").count(), 0);
+    assert_eq!(dict.words_with_prefix("กรรมการบ้าน").count(), 0);
+
+    assert_eq!(dict.common_prefixes_of("เอาการเอางานเสร็จ"), vec!["เอา", "เอาการเอางาน"]);
+    assert!(dict.common_prefixes_of("งาน").is_empty());
+}
+
+#[test]
+fn test_empty_sized_dict() {
+    let dict: SizedDict = Dict::new().into();
+
+    assert!(dict.is_empty());
+    assert!(!dict.contains("ก"));
+    assert_eq!(dict.iter().count(), 0);
+}
+
+#[test]
+fn test_remove() {
+    let words: Vec<String> = std::fs::read_to_string("data/th.txt").unwrap().lines().map(|l| l.to_owned()).collect();
+
+    // Remove each word and compare with a dict that never has the word
+    for removed in words.iter() {
+        let mut dict = Dict::load_txt("data/th.txt").unwrap();
+        let mut expected = Dict::new();
+        words.iter().filter(|w| *w != removed).for_each(|w| expected.add(w));
+
+        assert!(dict.remove(removed));
+        assert_eq!(dict, expected, "Unexpected structure after removing {}", removed);
+        assert!(!dict.remove(removed));
+    }
+}
+
+#[test]
+fn test_remove_merge() {
+    let mut dict = Dict::new();
+    dict.add("งาน");
+    dict.add("งานบ้าน");
+    dict.add("งานเรือน");
+    dict.add("งาช้าง");
+
+    // not a word
+    assert!(!dict.remove("งา"));
+    assert!(!dict.remove("งานบ"));
+    assert!(!dict.remove("งานบ้านเรือน"));
+    assert!(!dict.remove("ก"));
+
+    assert!(dict.remove("งาน"));
+    assert!(dict.remove("งานเรือน"));
+    // "งา" is now a prefix of only "ช้าง" and "นบ้าน"
+    assert_eq!(dict, Dict {
+        root: vec![
+            Node {
+                childs: Some(vec![
+                    Node {
+                        childs: Some(vec![]),
+                        terminal: Some(()),
+                        value: "ช้าง".to_owned()
+                    },
+                    Node {
+                        childs: Some(vec![]),
+                        terminal: Some(()),
+                        value: "นบ้าน".to_owned()
+                    }
+                ]),
+                terminal: None,
+                value: "งา".to_owned()
+            }
+        ]
+    });
+
+    // Only one word left so it is merged back into single node
+    assert!(dict.remove("งาช้าง"));
+    assert_eq!(dict, Dict {
+        root: vec![
+            Node {
+                childs: Some(vec![]),
+                terminal: Some(()),
+                value: "งานบ้าน".to_owned()
+            }
+        ]
+    });
+
+    assert!(dict.remove("งานบ้าน"));
+    assert_eq!(dict, Dict::new());
+}
+
+#[test]
+fn test_sized_dict_into_dict() {
+    let sized: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    let mut dict = Dict::from(sized);
+    assert_eq!(dict, Dict::load_txt("data/th.txt").unwrap());
+
+    dict.remove("กรรม");
+    dict.add("กรรมวิธี");
+    let sized = SizedDict::from(dict);
+    assert!(!sized.contains("กรรม"));
+    assert!(sized.contains("กรรมวิธี"));
+    assert!(sized.contains("กรรมกร"));
+    assert_eq!(sized.len(), 12);
+}
+
+#[test]
+fn test_get_mut_missing() {
+    // A failed lookup never change the tree, even on a leaf that has no child list
+    let make_dict = || Dict {root: vec![Node {childs: None, terminal: Some(1u32), value: "กรรม".to_owned()}]};
+    let mut dict = make_dict();
+    assert_eq!(dict.get_mut("กรรมการ"), None);
+    assert_eq!(dict.get_mut("กร"), None);
+    assert_eq!(dict, make_dict());
+    assert_eq!(dict.get_mut("กรรม"), Some(&mut 1));
+}
+
+#[test]
+fn test_payload() {
+    let mut dict = Dict::<u32>::default();
+    assert_eq!(dict.insert("กรรม", 1), None);
+    assert_eq!(dict.insert("กรรมการ", 2), None);
+    assert_eq!(dict.insert("กร", 3), None);
+    assert_eq!(dict.insert("กรรม", 4), Some(1));
+    dict.add("การ");
+    dict.add("กร");
+    *dict.get_mut("กรรมการ").unwrap() += 10;
+    assert_eq!(dict.get_mut("กรร"), None);
+
+    let dict = SizedDict::from(dict);
+    assert_eq!(dict.len(), 4);
+    assert_eq!(dict.get("กรรม"), Some(&4));
+    assert_eq!(dict.get("กร"), Some(&3));
+    assert_eq!(dict.get("การ"), Some(&0));
+    assert_eq!(dict.get("กรร"), None);
+
+    let text = "ทำกรรมการบ้าน";
+    let mut results = Vec::new();
+    dict.prefix_matches(text, "ทำ".len(), &mut results);
+    assert_eq!(results, vec![(12, &3), (18, &4), (27, &12)]);
+    assert_eq!(&text[6..results[2].0], "กรรมการ");
+}
+
+#[test]
+fn test_load_tsv() {
+    let dict = SizedDict::from(Dict::load_tsv("data/th.tsv").unwrap());
+    assert_eq!(dict.len(), 7);
+    assert_eq!(dict.get("กรรม"), Some(&WordInfo {frequency: 125, tags: vec!["NOUN".to_owned(), "VERB".to_owned()]}));
+    assert_eq!(dict.get("กรรมการ").unwrap().tags, vec!["NOUN", "VERB"]);
+    assert_eq!(dict.get("การ"), Some(&WordInfo {frequency: 500, tags: vec![]}));
+    assert_eq!(dict.get("อาจารย์"), Some(&WordInfo::default()));
+
+    let mut results = Vec::new();
+    dict.prefix_matches("กรรมกร", 0, &mut results);
+    assert_eq!(results.iter().map(|(end, info)| (*end, info.frequency)).collect::<Vec<_>>(), vec![(12, 125), (18, 35)]);
+
+    let path = std::env::temp_dir().join("tokenizer_invalid.tsv");
+    std::fs::write(&path, "กรรม\t1\nการ\tmany\n").unwrap();
+    let err = Dict::load_tsv_with(&path, LoadPolicy::Strict).unwrap_err();
+    assert!(matches!(&err, crate::Error::Entry {line: 2, kind: crate::EntryError::InvalidFrequency(freq), ..} if freq == "many"));
+    assert!(err.to_string().ends_with("tokenizer_invalid.tsv:2: invalid frequency \"many\""));
+    assert_eq!(std::io::Error::from(err).kind(), std::io::ErrorKind::InvalidData);
+    // The invalid line is ignored by default
+    assert_eq!(SizedDict::from(Dict::load_tsv(&path).unwrap()).len(), 1);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_policy() {
+    use crate::{EntryError, Error};
+
+    let path = std::env::temp_dir().join("tokenizer_policy.txt");
+    let mut content = "\u{feff}กรรม\r\n\nการ\n การ \nภาษา ไทย\n#แฮชแท็ก\n".as_bytes().to_vec();
+    content.extend_from_slice(b"\xe0\xb8\n");
+    content.extend_from_slice("กรรมการ".as_bytes());
+    std::fs::write(&path, content).unwrap();
+
+    let (dict, skipped) = Dict::load_txt_with(&path, LoadPolicy::Lenient).unwrap();
+    // A line that begin with `#` is a word, not a comment
+    assert_eq!(SizedDict::from(dict).iter().collect::<Vec<String>>(), vec!["#แฮชแท็ก", "กรรม", "กรรมการ", "การ"]);
+    assert!(skipped.is_empty());
+
+    let (dict, skipped) = Dict::load_txt_with(&path, LoadPolicy::SkipAndReport).unwrap();
+    assert_eq!(SizedDict::from(dict).len(), 4);
+    assert_eq!(
+        skipped.iter().map(|e| match e {
+            Error::Entry {line, kind, ..} => (*line, kind.clone()),
+            _ => unreachable!()
+        }).collect::<Vec<_>>(),
+        vec![
+            (2, EntryError::Empty),
+            (4, EntryError::Whitespace(" การ ".to_owned())),
+            (5, EntryError::Whitespace("ภาษา ไทย".to_owned())),
+            (7, EntryError::InvalidUtf8),
+        ]
+    );
+
+    let err = Dict::load_txt_with(&path, LoadPolicy::Strict).unwrap_err();
+    assert!(matches!(err, Error::Entry {line: 2, kind: EntryError::Empty, ..}));
+
+    std::fs::write(&path, "การ\nกรรม\nการ\n").unwrap();
+    let err = Dict::load_txt_with(&path, LoadPolicy::Strict).unwrap_err();
+    assert!(matches!(err, Error::Entry {line: 3, kind: EntryError::Duplicate(_), ..}));
+    std::fs::remove_file(&path).unwrap();
+
+    // A missing file is an I/O error with its path
+    let err = Dict::load_txt(&path).unwrap_err();
+    assert!(matches!(&err, Error::Io {path: Some(p), ..} if p == &path));
+    assert_eq!(std::io::Error::from(err).kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_frequencies() {
+    let dict = SizedDict::from(Dict::load_tsv("data/th.tsv").unwrap());
+    let mut results = Vec::new();
+    dict.frequencies_prefix("กรรมการ", 0, &mut results);
+    assert_eq!(results, vec![(12, 125), (21, 80)]);
+    assert_eq!(dict.total_frequency(), 1082);
+
+    // A dictionary without payload count every word once
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    let double_array = DoubleArray::from(&dict);
+    results.clear();
+    dict.frequencies_prefix("กรรมการ", 0, &mut results);
+    assert_eq!(results, vec![(12, 1), (21, 1)]);
+    assert_eq!(dict.total_frequency(), 12);
+    assert_eq!(double_array.total_frequency(), 12);
+}
+
+#[test]
+fn test_compile_txt() {
+    let path = std::env::temp_dir().join(format!("tokenizer_compile_txt_{}.bin", std::process::id()));
+    compile_txt("data/th.txt", &path).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let dict: SizedDict = Dict::load_txt("data/th.txt").unwrap().into();
+    assert_eq!(bytes, dict.to_bytes());
+    assert_eq!(compile_words(&dict.iter().collect::<Vec<String>>()), bytes);
+
+    let view = DictView::new(&bytes).unwrap();
+    let (mut expected, mut results) = (Vec::new(), Vec::new());
+    dict.terminals_prefix("กรรมการ", 0, &mut expected);
+    view.terminals_prefix("กรรมการ", 0, &mut results);
+    assert_eq!(results, expected);
+
+    // Comment is skipped the same way as build script of bundled dictionary
+    let txt = std::env::temp_dir().join(format!("tokenizer_compile_comment_{}.txt", std::process::id()));
+    std::fs::write(&txt, "# Thai words\nกรรม\n  # indented comment\nการ\n").unwrap();
+    compile_txt(&txt, &path).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(bytes, compile_words(&["กรรม", "การ"]));
+
+    // Comment is reported by stricter policy
+    std::fs::write(&txt, "กรรม\n  # indented comment\n\nการ\n").unwrap();
+    let skipped = compile_txt_with(&txt, &path, LoadPolicy::SkipAndReport).unwrap();
+    assert_eq!(
+        skipped.iter().map(|e| match e {
+            crate::Error::Entry {line, kind, ..} => (*line, kind.clone()),
+            _ => unreachable!()
+        }).collect::<Vec<_>>(),
+        vec![(2, crate::EntryError::Comment), (3, crate::EntryError::Empty)]
+    );
+    assert!(matches!(compile_txt_with(&txt, &path, LoadPolicy::Strict), Err(crate::Error::Entry {line: 2, kind: crate::EntryError::Comment, ..})));
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&txt).unwrap();
+
+    // A missing word list is an I/O error with its path
+    assert!(matches!(compile_txt("data/missing.txt", &path), Err(crate::Error::Io {path: Some(_), ..})));
+}
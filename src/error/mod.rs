@@ -0,0 +1,117 @@
+//! An error type of this crate.
+//!
+//! It is returned when loading a dictionary from text file. Unlike `std::io::Error`, it tell
+//! which file and which line cause the error. It can be converted into `std::io::Error`
+//! so it work with `?` in a function that return `std::io::Result`.
+
+use std::path::{Path, PathBuf};
+
+/// A problem of a single entry in dictionary file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EntryError {
+    /// The line is not a valid UTF-8.
+    InvalidUtf8,
+    /// The line has no word.
+    Empty,
+    /// The word is already in dictionary.
+    Duplicate(String),
+    /// The word has white space. Tokenizer split text by white space so such word never match.
+    Whitespace(String),
+    /// The frequency column is not an unsigned integer.
+    InvalidFrequency(String),
+    /// The line is a `#` comment. Only [compile_txt](dict/fn.compile_txt.html) treat such line as comment.
+    Comment,
+}
+
+impl std::fmt::Display for EntryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntryError::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            EntryError::Empty => write!(f, "empty entry"),
+            EntryError::Duplicate(word) => write!(f, "duplicate word {:?}", word),
+            EntryError::Whitespace(word) => write!(f, "word {:?} contains white space", word),
+            EntryError::InvalidFrequency(freq) => write!(f, "invalid frequency {:?}", freq),
+            EntryError::Comment => write!(f, "comment"),
+        }
+    }
+}
+
+/// An error of this crate.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading or writing a file failed.
+    Io {
+        /// A file that cause the error, if known.
+        path: Option<PathBuf>,
+        /// The underlying I/O error. Its kind is `InvalidData` if a binary dictionary is malformed.
+        source: std::io::Error,
+    },
+    /// An entry in dictionary file is malformed. See [LoadPolicy](dict/enum.LoadPolicy.html).
+    Entry {
+        /// A dictionary file that has the entry.
+        path: PathBuf,
+        /// A line number of the entry. The first line is 1.
+        line: usize,
+        /// What is wrong with the entry.
+        kind: EntryError,
+    },
+}
+
+impl Error {
+    pub(crate) fn io(path: &Path, source: std::io::Error) -> Error {
+        Error::Io {
+            path: Some(path.to_owned()),
+            source
+        }
+    }
+
+    pub(crate) fn entry(path: &Path, line: usize, kind: EntryError) -> Error {
+        Error::Entry {
+            path: path.to_owned(),
+            line,
+            kind
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io {path: Some(path), source} => write!(f, "{}: {}", path.display(), source),
+            Error::Io {path: None, source} => write!(f, "{}", source),
+            Error::Entry {path, line, kind} => write!(f, "{}:{}: {}", path.display(), line, kind),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io {source, ..} => Some(source),
+            Error::Entry {..} => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(source: std::io::Error) -> Error {
+        Error::Io {
+            path: None,
+            source
+        }
+    }
+}
+
+/// An I/O error keep its kind. A malformed entry become an error of kind `InvalidData`.
+impl From<Error> for std::io::Error {
+    fn from(error: Error) -> std::io::Error {
+        match error {
+            Error::Io {path: None, source} => source,
+            Error::Io {path: Some(_), ref source} => std::io::Error::new(source.kind(), error.to_string()),
+            Error::Entry {..} => std::io::Error::new(std::io::ErrorKind::InvalidData, error.to_string()),
+        }
+    }
+}
+
+/// A result with [Error](enum.Error.html) of this crate.
+pub type Result<T> = std::result::Result<T, Error>;
@@ -3,9 +3,18 @@
 //! It re-export two main module in root module.
 //! - `en` - A space based tokenizer.
 //! - `th` - A dictionary based tokenizer.
-mod dict;
+//! 
+//! The `dict` module contains dictionary representations that `th` tokenizer can use.
+//! Loading a dictionary from text file report [Error](enum.Error.html) with file name and line number.
+//! The `stream` module contains iterators that tokenize a text that come in pieces.
+pub mod dict;
+mod error;
 mod tokenizer;
 
+pub use self::error::{EntryError, Error, Result};
+
 pub use self::tokenizer::Tokenizer;
+pub use self::tokenizer::{Parallelism, SpanTokenizer, Token, TokenKind};
 pub use self::tokenizer::en;
+pub use self::tokenizer::stream;
 pub use self::tokenizer::th;
\ No newline at end of file
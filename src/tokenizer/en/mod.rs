@@ -16,4 +16,21 @@ impl super::Tokenizer for Tokenizer {
     fn tokenize<'a>(&self, text: &'a str) -> Vec<&'a str> {
         text.split_whitespace().collect()
     }
-}
\ No newline at end of file
+
+    fn tokenize_iter<'a>(&self, text: &'a str) -> impl Iterator<Item=&'a str> {
+        text.split_whitespace()
+    }
+
+    fn tokenize_into<'a>(&self, text: &'a str, tokens: &mut Vec<&'a str>) {
+        tokens.extend(text.split_whitespace());
+    }
+}
+
+impl super::SpanTokenizer for Tokenizer {
+    fn tokenize_spans<'a>(&self, text: &'a str) -> Vec<super::Token<'a>> {
+        super::make_tokens(text, text.split_whitespace().map(|word| {
+            let start = super::subslice_offset(text, word);
+            (start, start + word.len(), super::TokenKind::of(word))
+        }))
+    }
+}
@@ -7,199 +7,187 @@
 //! - English
 //! - Thai
 
-#[cfg(not(feature="single-thread"))]
-use std::sync::{Arc, RwLock, Weak};
-
-#[cfg(feature="single-thread")]
-use std::{cell::RefCell, rc::{Rc, Weak}};
-
-#[cfg(not(feature="single-thread"))]
-type MultiOwn<T> = Arc<RwLock<T>>;
-
-#[cfg(feature="single-thread")]
-type MultiOwn<T> = Rc<RefCell<T>>;
+/// An index of a node in [Tree](struct.Tree.html). The root node is always at index 0.
+pub(crate) type NodeId = usize;
 
 /// Currently supported tree operation.
 /// 
-/// Since Rust tree is not allow to have cyclic relation ship thus we need to wrap
-/// a node with `Weak` on one end and either `Rc` or `Arc` in another end.
-/// Since both of that types required interior mutability to mutate data.
-/// There's a dilemma on whether the trait signature shall be `&self` or `&mut self`.
-/// 
-/// In this case, we choose to avoid making a decision by consume `self` instead.
-/// This is to make it very obvious that the implementor shall clone the parent node.
-/// Otherwise, it will consume the parent node itself.
+/// Every node is owned by the tree and is referred to by its [NodeId](type.NodeId.html).
+/// A child only know an index of its parent so there's no cyclic reference nor interior mutability.
+/// Adding a child need `&mut self` and reading a node need only `&self`.
 pub trait TreeOp<T> {
-    /// Add a child node to tree. It will increment level but it will not increment unknown_count.
-    fn add_child(self, value: T) -> Self;
+    /// Add a child node with given value to `parent` node and return an id of new node.
+    /// Level of new node is one more than its parent.
+    fn add_child(&mut self, parent: NodeId, value: T) -> NodeId;
 
-    /// Get a level of node. Root node will have level 0. Child of root will have level 1 and so on.
-    fn level(&self) -> usize;
-
-    /// Consume itself and return a `Vec` that have a value from root till this node.
+    /// Return a `Vec` that have a value from root till given node.
     /// It will only have all the node value on this branch. All sibling nodes
     /// are excluded. The childs of this node are also excluded.
-    fn into_vec(self) -> Vec<T>;
+    /// 
+    /// If the given node is a root node or the node has no value, it'll panic.
+    fn to_vec(&self, node: NodeId) -> Vec<T>;
 }
 
-/// A Tree node that hold possibles tokenization result.
-/// 
-/// The relation between parent and child node in Rust requires either `Rc` or `Arc`.
-/// There's two feature gate to control this.
-/// - multi-thread - It will wrap this TreeNode in Arc<RwLock<TreeNode<T>>>
-/// - single-thread - It will wrap this TreeNode in `Rc<RefCell<TreeNode<T>>>`
-/// 
-/// Rust prohibit cyclic relationship. So either parent or child need to hold a `Weak` container type.
-/// In current design, we choose to make a parent hold a `Weak` reference to childs. This is to make
-/// it possible to partially drop some unused childs from parent.
+/// A node of [Tree](struct.Tree.html).
 /// 
-/// The root node will have no value, nor parent. Thus value and parent must be wrap inside Option.
-/// 
-/// Since both `Arc` and `Rc` only allow share immutable owned value but we need to add child node.
-/// We need to wrap it inside interior mutability kind of type. It's either `RefCell`, `Mutex`, or `RwLock`.
-/// As per above feature gate description, for `multi-thread`, it'll use `RwLock`. For `single-thread`,
-/// it'll use `RefCell`.
-/// 
-/// The node shall also know their own level so user don't have to traverse entire tree to find out the
-/// min and max depth of the tree. They only need to check on every leaves nodes.
+/// The node shall also know their own level so a branch can be collected into `Vec` without reallocation.
 #[derive(Debug)]
-#[allow(unused)]
-pub(crate) struct TreeNode<T> {
+struct TreeNode<T> {
     /// Level of node in current tree. Root node is at level 0. Childs of root is at level 1.
     level: usize,
     /// Current value of current node. Each node shall represent exactly one token.
     /// Root node will not have value.
     value: Option<T>,
+    /// Index of parent node. Root node has no parent.
+    parent: Option<NodeId>,
+}
 
-    /// Reference to parent node. If child node is not drop, the parent will always live.
-    parent: Option<MultiOwn<TreeNode<T>>>,
-    /// Reference to childs of current node. It is possible that the child is already dropped.
-    #[cfg(not(feature="single-thread"))]
-    childs: Vec<Weak<RwLock<TreeNode<T>>>>,
-    #[cfg(feature="single-thread")]
-    childs: Vec<Weak<RefCell<TreeNode<T>>>>
-}
-
-#[allow(unused)]
-impl<T> TreeNode<T> {
-    /// Since every tree operation require wrapping itself in `Arc<RwLock<>>`, it would
-    /// make user have easier usage by simply return `Arc<RwLock<TreeNode<T>>>`.
-    #[cfg(not(feature="single-thread"))]
-    #[allow(dead_code)]
-    fn root() -> Arc<RwLock<TreeNode<T>>> {
-        Arc::new(RwLock::new(TreeNode {
-            level: 0,
-            value: None,
-
-            parent: None,
-            childs: Vec::new()
-        }))
-    }
-    /// Since every tree operation require wrapping itself in `Rc<RefCell<>>`, it would
-    /// make user have easier usage by simply return `Rc<RefCell<TreeNode<T>>>`.
-    #[cfg(feature="single-thread")]
-    fn root() -> Rc<RefCell<TreeNode<T>>> {
-        Rc::new(RefCell::new(TreeNode {
-            level: 0,
-            value: None,
-
-            parent: None,
-            childs: Vec::new()
-        }))
-    }
-}
-
-/// Directly implement `TreeOp<T>` for both `Arc<RwLock<TreeNode<T>>>` and 
-/// `Rc<RefCell<TreeNode<T>>>` so caller can have easy access to some of
-/// node properties.
-impl<T> TreeOp<T> for MultiOwn<TreeNode<T>> where T: Copy {
-    #[cfg(not(feature="single-thread"))]
-    fn add_child(self, value: T) -> MultiOwn<TreeNode<T>> {
-        let level = self.read().unwrap().level;
-        let child = Arc::new(RwLock::new(TreeNode {
-            level: level + 1,
-            value: Some(value),
-            parent: Some(Arc::clone(&self)),
-            childs: Vec::new()
-        }));
+/// A tree that hold possibles tokenization result.
+/// 
+/// Every node is stored in one `Vec` and is referred to by its index. Adding a node is a push
+/// to the `Vec` so there's no lock nor allocation per node other than the `Vec` growth.
+/// The tree is `Send` and `Sync` whenever `T` is.
+/// 
+/// Every operation walk the tree iteratively so a deep tree, e.g. a tokenization result of
+/// a long text, never overflow the stack.
+#[derive(Debug)]
+pub(crate) struct Tree<T> {
+    nodes: Vec<TreeNode<T>>
+}
 
-        self.write().unwrap().childs.push(Arc::downgrade(&child));
+impl<T> Tree<T> {
+    /// An id of root node.
+    pub const ROOT: NodeId = 0;
 
-        child
+    /// Create a tree that has only root node.
+    pub fn new() -> Tree<T> {
+        Tree {
+            nodes: vec![TreeNode {
+                level: 0,
+                value: None,
+                parent: None,
+            }]
+        }
     }
-    #[cfg(feature="single-thread")]
-    fn add_child(self, value: T) -> MultiOwn<TreeNode<T>> {
-        let level = self.borrow().level;
-        let child = Rc::new(RefCell::new(TreeNode {
-            level: level + 1,
-            value: Some(value),
-            parent: Some(Rc::clone(&self)),
-            childs: Vec::new()
-        }));
+}
 
-        self.borrow_mut().childs.push(Rc::downgrade(&child));
+/// Copy value of each node into `Vec` so `T` must implement `Copy`.
+/// It is automatically implement for most of built-in Rust type, including borrowed value.
+impl<T> TreeOp<T> for Tree<T> where T: Copy {
+    fn add_child(&mut self, parent: NodeId, value: T) -> NodeId {
+        let id = self.nodes.len();
+        self.nodes.push(TreeNode {
+            level: self.nodes[parent].level + 1,
+            value: Some(value),
+            parent: Some(parent),
+        });
 
-        child
+        id
     }
 
-    fn level(&self) -> usize {
-        #[cfg(not(feature="single-thread"))]
-        return self.read().unwrap().level;
-        #[cfg(feature="single-thread")]
-        return self.borrow().level;
-    }
+    fn to_vec(&self, node: NodeId) -> Vec<T> {
+        if self.nodes[node].value.is_none() {
+            panic!("The given node has no value. Either it is a root node or it is improper constructed node.");
+        }
+
+        let mut v = Vec::with_capacity(self.nodes[node].level);
+        let mut current = Some(node);
+
+        // Walk up until root node which has no value
+        while let Some(TreeNode {value: Some(value), parent, ..}) = current.map(|id| &self.nodes[id]) {
+            v.push(*value);
+            current = *parent;
+        }
 
-    fn into_vec(self) -> Vec<T> {
-        #[cfg(not(feature="single-thread"))]
-        return (&*self.read().unwrap()).into();
-        #[cfg(feature="single-thread")]
-        return (&*self.borrow()).into();
+        v.reverse();
+        v
     }
 }
 
-/// Convert branch of tree from given node up to root node into a Vec<T>.
+/// How a tokenizer spread its work across threads.
 /// 
-/// If the given node is a root node or the node has no value, it'll panic.
+/// A tokenizer split text by white space then tokenize each chunk independently.
+/// Those chunks can be tokenized on many threads. The result is the same regardless of
+/// parallelism. Only `Sequential` is available without `multi-thread` feature.
 /// 
-/// This is shallow type conversion thus `T` must implement `Copy`.
-/// It is automatically implement for most of built-in Rust type, including borrowed value.
-impl<T> std::convert::From<&TreeNode<T>> for Vec<T> where T: Copy {
-
-    fn from(node: &TreeNode<T>) -> Vec<T> {
-        let mut v = Vec::with_capacity(node.level);
-        
-        #[cfg(not(feature="single-thread"))]
-        fn traverse_tree<T>(node: &MultiOwn<TreeNode<T>>, vec: &mut Vec<T>) where T: Copy {
-            let actual_node = node.read().unwrap();
-            
-            if let Some(ref parent) = actual_node.parent {
-                traverse_tree(parent, vec);
-                // Add value here as it is not a root node. 
-                vec.push(*actual_node.value.as_ref().unwrap());
-            }
-        }
-        #[cfg(feature="single-thread")]
-        fn traverse_tree<T>(node: &MultiOwn<TreeNode<T>>, vec: &mut Vec<T>) where T: Copy {
-            let actual_node = node.borrow();
-            
-            if let Some(ref parent) = actual_node.parent {
-                traverse_tree(parent, vec);
-                // Add value here as it is not a root node. 
-                vec.push(*actual_node.value.as_ref().unwrap());
-            }
-        }
+/// Other variants depend on enabled features so a `match` outside this crate need a wildcard arm.
+/// It keep compiling when any other crate turn `multi-thread` feature on.
+/// ```
+/// use tokenizer::Parallelism;
+/// fn describe(parallelism: &Parallelism) -> &'static str {
+///     match parallelism {
+///         Parallelism::Sequential => "sequential",
+///         _ => "parallel"
+///     }
+/// }
+/// assert_eq!(describe(&Parallelism::Sequential), "sequential");
+/// ```
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum Parallelism {
+    /// Tokenize every chunk on calling thread.
+    Sequential,
+    /// Tokenize chunks on Rayon global thread pool.
+    #[cfg(feature="multi-thread")]
+    Global,
+    /// Tokenize chunks on given Rayon thread pool.
+    #[cfg(feature="multi-thread")]
+    Pool(std::sync::Arc<rayon::ThreadPool>),
+}
 
-        if let Some(ref parent) = node.parent {
-            traverse_tree(parent, &mut v);
+impl Default for Parallelism {
+    /// It is `Global` if `multi-thread` feature is enabled. Otherwise, it is `Sequential`.
+    fn default() -> Parallelism {
+        #[cfg(feature="multi-thread")]
+        return Parallelism::Global;
+        #[cfg(not(feature="multi-thread"))]
+        return Parallelism::Sequential;
+    }
+}
+
+impl Parallelism {
+    /// Call `f` on each white space separated chunk of `text` and append every item it return
+    /// into `out`. The order of items follow the order of chunks regardless of parallelism.
+    pub(crate) fn extend_chunks<'a, T, F, I>(&self, text: &'a str, out: &mut Vec<T>, f: F)
+    where T: Send, F: Fn(&'a str) -> I + Send + Sync, I: IntoIterator<Item=T> {
+        #[cfg(feature="multi-thread")]
+        fn par_extend<'a, T, F, I>(text: &'a str, out: &mut Vec<T>, f: F)
+        where T: Send, F: Fn(&'a str) -> I + Send + Sync, I: IntoIterator<Item=T> {
+            use rayon::prelude::*;
+
+            out.par_extend(text.par_split_whitespace().flat_map_iter(f));
         }
-        
-        if node.value.is_none() {
-            panic!("The given node has no value. Either it is a root node or it is improper constructed node.");
+
+        match self {
+            Parallelism::Sequential => out.extend(text.split_whitespace().flat_map(f)),
+            #[cfg(feature="multi-thread")]
+            Parallelism::Global => par_extend(text, out, f),
+            #[cfg(feature="multi-thread")]
+            Parallelism::Pool(pool) => pool.install(|| par_extend(text, out, f)),
         }
+    }
+
+    /// Call `f` on each item of `items` and return their results in the same order as `items`.
+    /// 
+    /// When run in parallel, each thread take at least `min_len` items at a time.
+    #[cfg_attr(not(feature="multi-thread"), allow(unused_variables))]
+    pub(crate) fn map_batch<'a, T, R, F>(&self, items: &'a [T], min_len: usize, f: F) -> Vec<R>
+    where T: Sync, R: Send, F: Fn(&'a T) -> R + Send + Sync {
+        #[cfg(feature="multi-thread")]
+        fn par_map<'a, T, R, F>(items: &'a [T], min_len: usize, f: F) -> Vec<R>
+        where T: Sync, R: Send, F: Fn(&'a T) -> R + Send + Sync {
+            use rayon::prelude::*;
 
-        v.push(*node.value.as_ref().unwrap());
+            items.par_iter().with_min_len(min_len).map(f).collect()
+        }
 
-        v.into()
+        match self {
+            Parallelism::Sequential => items.iter().map(f).collect(),
+            #[cfg(feature="multi-thread")]
+            Parallelism::Global => par_map(items, min_len, f),
+            #[cfg(feature="multi-thread")]
+            Parallelism::Pool(pool) => pool.install(|| par_map(items, min_len, f)),
+        }
     }
 }
 
@@ -208,9 +196,262 @@ pub trait Tokenizer {
     /// Tokenize given `text` and return a `Vec<&str>` where each `&str` inside
     /// a `Vec` is a slice from given text.
     fn tokenize<'a>(&self, text: &'a str) -> Vec<&'a str>;
+
+    /// Tokenize given `text` and return an iterator that yield each token lazily.
+    /// 
+    /// It is useful when only first few tokens are needed or when tokens are folded into
+    /// something else. Default implementation simply iterate over result of
+    /// [tokenize](trait.Tokenizer.html#tymethod.tokenize).
+    /// ```
+    /// use tokenizer::{Tokenizer, en};
+    /// assert_eq!(en::Tokenizer.tokenize_iter("one two three").nth(1), Some("two"));
+    /// ```
+    fn tokenize_iter<'a>(&self, text: &'a str) -> impl Iterator<Item=&'a str> where Self: Sized {
+        self.tokenize(text).into_iter()
+    }
+
+    /// Tokenize given `text` and append each token to `tokens`.
+    /// 
+    /// Existing content of `tokens` is kept. Caller can clear and reuse the same `Vec`
+    /// across many calls to avoid allocating new one each time.
+    fn tokenize_into<'a>(&self, text: &'a str, tokens: &mut Vec<&'a str>) {
+        tokens.extend(self.tokenize(text));
+    }
+
+    /// Tokenize a text that come as an iterator of chunks, e.g. `&str` or `String`.
+    /// 
+    /// It return an iterator of owned tokens. A chunk may end anywhere, even in the middle of a word.
+    /// The tokens are the same as tokenizing concatenation of all chunks.
+    /// See [stream](stream/index.html) module for detail.
+    /// ```
+    /// use tokenizer::{Tokenizer, en};
+    /// let tokens: Vec<String> = en::Tokenizer.tokenize_chunks(vec!["Hel", "lo wor", "ld"]).collect();
+    /// assert_eq!(tokens, vec!["Hello", "world"]);
+    /// ```
+    fn tokenize_chunks<I>(&self, chunks: I) -> stream::ChunkTokens<'_, Self, I::IntoIter> where Self: Sized, I: IntoIterator, I::Item: AsRef<str> {
+        stream::ChunkTokens::new(self, chunks.into_iter())
+    }
+
+    /// Tokenize a text read from given `BufRead`, e.g. a `BufReader` of a large file.
+    /// 
+    /// It return an iterator of owned tokens. Reading is done lazily as tokens are consumed.
+    /// See [stream](stream/index.html) module for detail.
+    fn tokenize_reader<R>(&self, reader: R) -> stream::ReadTokens<'_, Self, R> where Self: Sized, R: std::io::BufRead {
+        stream::ReadTokens::new(self, reader)
+    }
+}
+
+/// A kind of token.
+/// 
+/// A dictionary based tokenizer can tell whether a word is `Known` or `Unknown`.
+/// The other kinds are decided by characters in the token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// A word found in dictionary.
+    Known,
+    /// A word that is not found in dictionary nor is any other kind.
+    Unknown,
+    /// A number in Arabic or Thai digits. It may have `.` or `,` between digits, e.g. `1,000.50` or `๑๒๓`.
+    Number,
+    /// One or more punctuation characters.
+    Punctuation,
+    /// A word written in Latin alphabet.
+    Latin,
+    /// One or more white space characters.
+    Whitespace,
+}
+
+/// A class of single character. It is used to identify [TokenKind](enum.TokenKind.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CharClass {
+    Whitespace,
+    Digit,
+    Punctuation,
+    Latin,
+    Other
+}
+
+impl CharClass {
+    fn of(c: char) -> CharClass {
+        match c {
+            c if c.is_whitespace() => CharClass::Whitespace,
+            '0'..='9' | '๐'..='๙' => CharClass::Digit,
+            // Thai punctuation: paiyannoi, fongman, angkhankhu and khomut
+            'ฯ' | '๏' | '๚' | '๛' => CharClass::Punctuation,
+            c if c.is_ascii_punctuation() => CharClass::Punctuation,
+            // Latin-1 punctuation and general punctuation block
+            '\u{a1}'..='\u{bf}' | '\u{d7}' | '\u{f7}' | '\u{2010}'..='\u{205e}' => CharClass::Punctuation,
+            c if c.is_ascii_alphabetic() => CharClass::Latin,
+            // Latin-1 supplement, Latin extended-A and Latin extended-B letters
+            '\u{c0}'..='\u{24f}' => CharClass::Latin,
+            _ => CharClass::Other
+        }
+    }
+}
+
+/// Return true if given text is entirely a number. It allows a single `.` or `,` between digits.
+fn is_number(text: &str) -> bool {
+    let mut prev = None;
+
+    for c in text.chars() {
+        match CharClass::of(c) {
+            CharClass::Digit => {},
+            // A separator must be preceded by digit
+            _ if (c == '.' || c == ',') && prev == Some(CharClass::Digit) => {},
+            _ => return false
+        }
+        prev = Some(CharClass::of(c));
+    }
+
+    // Number must end with digit
+    prev == Some(CharClass::Digit)
+}
+
+impl TokenKind {
+    /// Classify given text by its characters.
+    /// 
+    /// It never return `Known` as it need a dictionary to tell. It return
+    /// `Unknown` if the text is not entirely a number, punctuation, white space or
+    /// a Latin word. A Latin word is a word that has at least one Latin letter and
+    /// the rest are Latin letters, digits or punctuation, e.g. `Hello,` or `COVID-19`.
+    pub fn of(text: &str) -> TokenKind {
+        let mut classes = text.chars().map(CharClass::of);
+
+        if text.is_empty() {
+            TokenKind::Unknown
+        } else if classes.clone().all(|c| c == CharClass::Whitespace) {
+            TokenKind::Whitespace
+        } else if is_number(text) {
+            TokenKind::Number
+        } else if classes.clone().all(|c| c == CharClass::Punctuation) {
+            TokenKind::Punctuation
+        } else if classes.clone().any(|c| c == CharClass::Latin) 
+                && classes.all(|c| c == CharClass::Latin || c == CharClass::Digit || c == CharClass::Punctuation) {
+            TokenKind::Latin
+        } else {
+            TokenKind::Unknown
+        }
+    }
+}
+
+/// Split given text into runs of characters of the same class and classify each run.
+/// 
+/// The return value is a `Vec` of byte range and kind of each run.
+/// A `.` or `,` that is between two digits is part of a number.
+/// Characters that are none of the class are `Unknown`.
+/// 
+/// For example, `abc123ก` will become `abc`, `123`, and `ก`. It is used where splitting is
+/// asked for, e.g. to find punctuation separators, and to split digits and punctuation out of
+/// an unknown word. It is never used to split a word found by dictionary based tokenizer.
+pub(crate) fn split_kinds(text: &str) -> Vec<(usize, usize, TokenKind)> {
+    fn kind(class: CharClass) -> TokenKind {
+        match class {
+            CharClass::Whitespace => TokenKind::Whitespace,
+            CharClass::Digit => TokenKind::Number,
+            CharClass::Punctuation => TokenKind::Punctuation,
+            CharClass::Latin => TokenKind::Latin,
+            CharClass::Other => TokenKind::Unknown
+        }
+    }
+
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut current: Option<CharClass> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let mut class = CharClass::of(c);
+
+        if current == Some(CharClass::Digit) && (c == '.' || c == ',') {
+            if let Some((_, next)) = chars.peek() {
+                if CharClass::of(*next) == CharClass::Digit {
+                    // decimal point or thousand separator
+                    class = CharClass::Digit;
+                }
+            }
+        }
+
+        match current {
+            Some(cur) if cur == class => {},
+            Some(cur) => {
+                runs.push((start, i, kind(cur)));
+                start = i;
+                current = Some(class);
+            },
+            None => current = Some(class)
+        }
+    }
+
+    if let Some(cur) = current {
+        runs.push((start, text.len(), kind(cur)));
+    }
+
+    runs
+}
+
+/// A token along with its position in the original text.
+/// 
+/// It is a result of [SpanTokenizer](trait.SpanTokenizer.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token<'a> {
+    /// A slice from original text that this token represent.
+    pub text: &'a str,
+    /// A byte offset of first byte of this token in the original text.
+    pub start: usize,
+    /// A byte offset right after last byte of this token. `&text[start..end]` is this token.
+    pub end: usize,
+    /// A number of chars in the original text that come before this token.
+    pub char_offset: usize,
+    /// An index of this token in tokenization result. The first token is at index 0.
+    pub index: usize,
+    /// A kind of this token.
+    pub kind: TokenKind,
+}
+
+/// A trait for Tokenizer that can also tell where each token is in the original text.
+pub trait SpanTokenizer: Tokenizer {
+    /// Tokenize given `text` and return a `Vec<Token>`. Each token has the same order
+    /// as the result of [tokenize](trait.Tokenizer.html#tymethod.tokenize) and carry its
+    /// byte offset, char offset, index and kind.
+    fn tokenize_spans<'a>(&self, text: &'a str) -> Vec<Token<'a>>;
+}
+
+/// Return a byte offset of `slice` inside `text`.
+/// 
+/// The `slice` must be a sub-slice of `text`, e.g. a value returned from `split_whitespace`.
+#[inline(always)]
+pub(crate) fn subslice_offset(text: &str, slice: &str) -> usize {
+    slice.as_ptr() as usize - text.as_ptr() as usize
+}
+
+/// Make a `Vec<Token>` from byte ranges of `text` and kind of each range.
+/// 
+/// The ranges must be in ascending order and must not overlap.
+/// The char offset is counted as it goes so entire text is walked only once.
+pub(crate) fn make_tokens<'a, I>(text: &'a str, ranges: I) -> Vec<Token<'a>> where I: IntoIterator<Item=(usize, usize, TokenKind)> {
+    let mut last_end = 0;
+    let mut char_offset = 0;
+
+    ranges.into_iter().enumerate().map(|(index, (start, end, kind))| {
+        // count chars between previous token and this token, e.g. white space
+        char_offset += text[last_end..start].chars().count();
+        let token = Token {
+            text: &text[start..end],
+            start,
+            end,
+            char_offset,
+            index,
+            kind
+        };
+        char_offset += token.text.chars().count();
+        last_end = end;
+
+        token
+    }).collect()
 }
 
 pub mod en;
+pub mod stream;
 pub mod th;
 
 #[cfg(test)]
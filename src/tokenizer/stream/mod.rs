@@ -0,0 +1,217 @@
+//! Tokenize a text that come in pieces, e.g. a large file or a network stream.
+//!
+//! Every tokenizer in this crate split text by white space before anything else so a text
+//! can be safely cut right before a word that follow a white space. Each piece is buffered
+//! until such a cut is found. Everything before the cut is tokenized and the rest is kept
+//! until next piece arrive. Therefore, a word that span across two pieces is never cut and
+//! the result is the same as tokenizing an entire text at once.
+//!
+//! The buffer only hold text since the last white space so memory usage is bounded by the
+//! longest run of text without white space, not by the size of entire text.
+//!
+//! Use [tokenize_chunks](../trait.Tokenizer.html#method.tokenize_chunks) for an iterator of
+//! `&str` or `String`, and [tokenize_reader](../trait.Tokenizer.html#method.tokenize_reader)
+//! for any `BufRead`.
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+use super::Tokenizer;
+
+/// Return a byte offset right before the last word that follow a white space in `text`.
+///
+/// Only a cut at or after `from` is considered. It return None if there's no such cut.
+fn safe_cut(text: &str, from: usize) -> Option<usize> {
+    let mut followed_by_word = false;
+
+    for (i, c) in text[from..].char_indices().rev() {
+        if c.is_whitespace() {
+            if followed_by_word {
+                return Some(from + i + c.len_utf8())
+            }
+        } else {
+            followed_by_word = true;
+            continue
+        }
+
+        followed_by_word = false;
+    }
+
+    None
+}
+
+/// A text that is not yet tokenized along with tokens that is not yet consumed.
+struct Pending<'t, T: ?Sized> {
+    tokenizer: &'t T,
+    text: String,
+    tokens: VecDeque<String>,
+}
+
+impl<'t, T: Tokenizer + ?Sized> Pending<'t, T> {
+    fn new(tokenizer: &'t T) -> Pending<'t, T> {
+        Pending {
+            tokenizer,
+            text: String::new(),
+            tokens: VecDeque::new()
+        }
+    }
+
+    /// Append a chunk to pending text then tokenize every complete word in it.
+    fn push(&mut self, chunk: &str) {
+        // A cut may be right after the last char of previous text
+        let from = self.text.char_indices().next_back().map_or(0, |(i, _)| i);
+        self.text.push_str(chunk);
+
+        if let Some(cut) = safe_cut(&self.text, from) {
+            self.tokenize(cut);
+        }
+    }
+
+    /// Tokenize every remaining text. It must be called after the last chunk is pushed.
+    fn finish(&mut self) {
+        self.tokenize(self.text.len());
+    }
+
+    fn tokenize(&mut self, cut: usize) {
+        self.tokens.extend(self.tokenizer.tokenize(&self.text[..cut]).into_iter().map(str::to_owned));
+        self.text.drain(..cut);
+    }
+}
+
+/// An iterator of owned tokens from an iterator of text chunks.
+///
+/// It is made by [tokenize_chunks](../trait.Tokenizer.html#method.tokenize_chunks).
+pub struct ChunkTokens<'t, T: ?Sized, I> {
+    chunks: I,
+    pending: Pending<'t, T>,
+    done: bool,
+}
+
+impl<'t, T: Tokenizer + ?Sized, I> ChunkTokens<'t, T, I> {
+    pub(crate) fn new(tokenizer: &'t T, chunks: I) -> ChunkTokens<'t, T, I> {
+        ChunkTokens {
+            chunks,
+            pending: Pending::new(tokenizer),
+            done: false
+        }
+    }
+}
+
+impl<T, I> Iterator for ChunkTokens<'_, T, I> where T: Tokenizer + ?Sized, I: Iterator, I::Item: AsRef<str> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            if let Some(token) = self.pending.tokens.pop_front() {
+                return Some(token)
+            }
+
+            if self.done {
+                return None
+            }
+
+            match self.chunks.next() {
+                Some(chunk) => self.pending.push(chunk.as_ref()),
+                None => {
+                    self.done = true;
+                    self.pending.finish();
+                }
+            }
+        }
+    }
+}
+
+/// An iterator of owned tokens from a `BufRead`.
+///
+/// It is made by [tokenize_reader](../trait.Tokenizer.html#method.tokenize_reader).
+/// Bytes are decoded as UTF-8. A multi-bytes char that is split between two reads is kept
+/// until the rest of it arrive. An invalid UTF-8 sequence yield an `io::Error` of kind
+/// `InvalidData`. After any error is returned, the iterator end. Pending text that is not yet
+/// followed by a white space is not tokenized.
+pub struct ReadTokens<'t, T: ?Sized, R> {
+    reader: R,
+    /// Bytes that is read but not yet decoded. It is an incomplete char at the end of last read.
+    bytes: Vec<u8>,
+    pending: Pending<'t, T>,
+    done: bool,
+}
+
+impl<'t, T: Tokenizer + ?Sized, R: BufRead> ReadTokens<'t, T, R> {
+    pub(crate) fn new(tokenizer: &'t T, reader: R) -> ReadTokens<'t, T, R> {
+        ReadTokens {
+            reader,
+            bytes: Vec::new(),
+            pending: Pending::new(tokenizer),
+            done: false
+        }
+    }
+
+    /// Read next chunk and push every complete char into pending text.
+    /// It return false when the reader reach the end.
+    fn read_chunk(&mut self) -> std::io::Result<bool> {
+        let read = match self.reader.fill_buf() {
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => return Ok(true),
+            Err(e) => return Err(e)
+        };
+
+        if read.is_empty() {
+            if self.bytes.is_empty() {
+                return Ok(false)
+            }
+
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "stream did not end with a complete UTF-8 char"))
+        }
+
+        let len = read.len();
+        self.bytes.extend_from_slice(read);
+        self.reader.consume(len);
+
+        let valid = match std::str::from_utf8(&self.bytes) {
+            Ok(text) => {
+                self.pending.push(text);
+                text.len()
+            },
+            // The last char is incomplete, the rest of it is in the next read.
+            Err(e) if e.error_len().is_none() => {
+                let valid = e.valid_up_to();
+                self.pending.push(std::str::from_utf8(&self.bytes[..valid]).unwrap());
+                valid
+            },
+            Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        };
+        self.bytes.drain(..valid);
+
+        Ok(true)
+    }
+}
+
+impl<T: Tokenizer + ?Sized, R: BufRead> Iterator for ReadTokens<'_, T, R> {
+    type Item = std::io::Result<String>;
+
+    fn next(&mut self) -> Option<std::io::Result<String>> {
+        loop {
+            if let Some(token) = self.pending.tokens.pop_front() {
+                return Some(Ok(token))
+            }
+
+            if self.done {
+                return None
+            }
+
+            match self.read_chunk() {
+                Ok(true) => (),
+                Ok(false) => {
+                    self.done = true;
+                    self.pending.finish();
+                },
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
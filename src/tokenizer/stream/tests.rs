@@ -0,0 +1,85 @@
+use super::*;
+use crate::tokenizer::{en, th};
+use std::io::BufReader;
+
+#[test]
+fn test_safe_cut() {
+    assert_eq!(safe_cut("ab cd", 0), Some(3));
+    assert_eq!(safe_cut("ab  cd ", 0), Some(4));
+    assert_eq!(safe_cut("ab cd", 3), None);
+    assert_eq!(safe_cut("abcd  ", 0), None);
+    assert_eq!(safe_cut("ก\u{a0}ข", 0), Some(5));
+}
+
+#[test]
+fn test_chunks() {
+    let text = "  Hello, wörld  again ";
+    let expected = en::Tokenizer.tokenize(text);
+
+    // Split text at every char boundary
+    for (i, _) in text.char_indices() {
+        let tokens: Vec<String> = en::Tokenizer.tokenize_chunks(vec![&text[..i], &text[i..]]).collect();
+        assert_eq!(tokens, expected);
+    }
+
+    let tokens: Vec<String> = en::Tokenizer.tokenize_chunks(text.chars().map(String::from)).collect();
+    assert_eq!(tokens, expected);
+    assert_eq!(en::Tokenizer.tokenize_chunks(Vec::<&str>::new()).count(), 0);
+}
+
+#[test]
+fn test_th_chunks() {
+    let tokenizer = th::Tokenizer::from(&["กำลัง", "การ", "บ้าน", "การบ้าน", "ทำ"][..]);
+    let text = "กำลังทำการบ้าน ทำการบ้าน";
+    let expected = tokenizer.tokenize(text);
+    assert_eq!(expected, vec!["กำลัง", "ทำ", "การบ้าน", "ทำ", "การบ้าน"]);
+
+    // A word that span two chunks is never cut
+    for (i, _) in text.char_indices() {
+        let tokens: Vec<String> = tokenizer.tokenize_chunks(vec![&text[..i], &text[i..]]).collect();
+        assert_eq!(tokens, expected);
+    }
+
+    // A run of white space is never split either
+    let tokenizer = tokenizer.with_separators(th::Separators::Whitespace);
+    let text = "ทำ  การบ้าน   ";
+    let expected = tokenizer.tokenize(text);
+    for (i, _) in text.char_indices() {
+        let tokens: Vec<String> = tokenizer.tokenize_chunks(vec![&text[..i], &text[i..]]).collect();
+        assert_eq!(tokens, expected);
+        assert_eq!(tokens.concat(), text);
+    }
+}
+
+#[test]
+fn test_reader() {
+    let tokenizer = th::Tokenizer::from(&["กำลัง", "การ", "บ้าน", "การบ้าน", "ทำ"][..]);
+    let text = "กำลังทำการบ้าน\nทำการบ้าน  COVID-19";
+    let expected = tokenizer.tokenize(text);
+
+    // Each read return a single byte so every multi-bytes char is split
+    let reader = BufReader::with_capacity(1, text.as_bytes());
+    let tokens: Vec<String> = tokenizer.tokenize_reader(reader).map(|t| t.unwrap()).collect();
+    assert_eq!(tokens, expected);
+
+    let reader = BufReader::with_capacity(7, text.as_bytes());
+    let tokens: Vec<String> = tokenizer.tokenize_reader(reader).map(|t| t.unwrap()).collect();
+    assert_eq!(tokens, expected);
+}
+
+#[test]
+fn test_reader_invalid_utf8() {
+    let bytes: &[u8] = b"one two \xff three";
+    let mut tokens = en::Tokenizer.tokenize_reader(BufReader::with_capacity(4, bytes));
+    // Only a word followed by white space within valid bytes is returned before an error
+    assert_eq!(tokens.next().unwrap().unwrap(), "one");
+    assert_eq!(tokens.next().unwrap().unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    assert!(tokens.next().is_none());
+
+    // Stream end in the middle of a char
+    let bytes = &"ab cd ก".as_bytes()[..7];
+    let result: Vec<std::io::Result<String>> = en::Tokenizer.tokenize_reader(bytes).collect();
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[0].as_ref().unwrap(), "ab");
+    assert_eq!(result[1].as_ref().unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+}
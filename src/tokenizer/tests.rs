@@ -1,35 +1,77 @@
 use super::*;
 
 
-#[cfg(not(feature="single-thread"))]
 #[test]
 #[should_panic(expected="The given node has no value. Either it is a root node or it is improper constructed node.")]
 fn test_tree() {
-    let root = TreeNode::root();
-    let a = Arc::clone(&root).add_child("a");
-    let one = Arc::clone(&a).add_child("1");
-    let two = Arc::clone(&a).add_child("2");
-    let b = Arc::clone(&root).add_child("b");
-    assert_eq!(Vec::from(&*two.read().unwrap()), vec!["a", "2"]);
-    assert_eq!(Vec::from(&*one.read().unwrap()), vec!["a", "1"]);
-    assert_eq!(Vec::from(&*b.read().unwrap()), vec!["b"]);
+    let mut tree = Tree::new();
+    let a = tree.add_child(Tree::<&str>::ROOT, "a");
+    let one = tree.add_child(a, "1");
+    let two = tree.add_child(a, "2");
+    let b = tree.add_child(Tree::<&str>::ROOT, "b");
+    assert_eq!(tree.to_vec(two), vec!["a", "2"]);
+    assert_eq!(tree.to_vec(one), vec!["a", "1"]);
+    assert_eq!(tree.to_vec(b), vec!["b"]);
 
     // This should cause panic as root node has no value
-    Vec::<&str>::from(&*TreeNode::root().read().unwrap());
+    let _ = tree.to_vec(Tree::<&str>::ROOT);
 }
-#[cfg(feature="single-thread")]
+
 #[test]
-#[should_panic(expected="The given node has no value. Either it is a root node or it is improper constructed node.")]
-fn test_tree() {
-    let root = TreeNode::root();
-    let a = Rc::clone(&root).add_child("a");
-    let one = Rc::clone(&a).add_child("1");
-    let two = Rc::clone(&a).add_child("2");
-    let b = Rc::clone(&root).add_child("b");
-    assert_eq!(Vec::from(&*two.borrow()), vec!["a", "2"]);
-    assert_eq!(Vec::from(&*one.borrow()), vec!["a", "1"]);
-    assert_eq!(Vec::from(&*b.borrow()), vec!["b"]);
+fn test_deep_tree() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
 
-    // This should cause panic as root node has no value
-    Vec::<&str>::from(&*TreeNode::root().borrow());
-}
\ No newline at end of file
+    let mut tree = Tree::new();
+    let leaf = (0..1_000_000).fold(Tree::<usize>::ROOT, |parent, i| tree.add_child(parent, i));
+    assert_send_sync(&tree);
+    assert!(tree.to_vec(leaf).into_iter().eq(0..1_000_000));
+}
+
+#[test]
+fn test_en_tokenize_spans() {
+    let tokens = en::Tokenizer.tokenize_spans("  Hello, wörld  again ");
+    assert_eq!(
+        tokens,
+        vec![
+            Token {text: "Hello,", start: 2, end: 8, char_offset: 2, index: 0, kind: TokenKind::Latin},
+            Token {text: "wörld", start: 9, end: 15, char_offset: 9, index: 1, kind: TokenKind::Latin},
+            Token {text: "again", start: 17, end: 22, char_offset: 16, index: 2, kind: TokenKind::Latin},
+        ]
+    );
+}
+
+#[test]
+fn test_en_tokenize_iter() {
+    let text = "  Hello, wörld  again ";
+    assert_eq!(en::Tokenizer.tokenize_iter(text).collect::<Vec<_>>(), en::Tokenizer.tokenize(text));
+
+    let mut tokens = Vec::new();
+    en::Tokenizer.tokenize_into(text, &mut tokens);
+    en::Tokenizer.tokenize_into("again", &mut tokens);
+    assert_eq!(tokens, vec!["Hello,", "wörld", "again", "again"]);
+}
+
+#[test]
+fn test_token_kind() {
+    assert_eq!(TokenKind::of("1,000.50"), TokenKind::Number);
+    assert_eq!(TokenKind::of("๒๕๖๓"), TokenKind::Number);
+    assert_eq!(TokenKind::of("...ฯ"), TokenKind::Punctuation);
+    assert_eq!(TokenKind::of("COVID-19"), TokenKind::Latin);
+    assert_eq!(TokenKind::of(" \t"), TokenKind::Whitespace);
+    assert_eq!(TokenKind::of("มากๆ"), TokenKind::Unknown);
+    assert_eq!(TokenKind::of("easyมาก"), TokenKind::Unknown);
+}
+
+#[test]
+fn test_split_kinds() {
+    let text = "abc1,000.5มากๆ!! x";
+    let runs: Vec<(&str, TokenKind)> = split_kinds(text).into_iter().map(|(s, e, k)| (&text[s..e], k)).collect();
+    assert_eq!(runs, vec![
+        ("abc", TokenKind::Latin),
+        ("1,000.5", TokenKind::Number),
+        ("มากๆ", TokenKind::Unknown),
+        ("!!", TokenKind::Punctuation),
+        (" ", TokenKind::Whitespace),
+        ("x", TokenKind::Latin),
+    ]);
+}
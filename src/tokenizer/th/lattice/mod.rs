@@ -0,0 +1,78 @@
+//! A word lattice of Thai text.
+//! 
+//! A [Lattice](struct.Lattice.html) has every candidate word that dictionary allow, not just the
+//! chosen segmentation. It is made by [Tokenizer::lattice](../struct.Tokenizer.html#method.lattice).
+//! It can be exported to Graphviz DOT by [to_dot](struct.Lattice.html#method.to_dot) to inspect ambiguities.
+
+/// A candidate word in [Lattice](struct.Lattice.html). It goes from vertex `start` to vertex `end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge<'a> {
+    /// A slice from original text that this edge represent.
+    pub text: &'a str,
+    /// A byte offset of the vertex that this edge leave.
+    pub start: usize,
+    /// A byte offset of the vertex that this edge reach. `&text[start..end]` is this word.
+    pub end: usize,
+    /// True if it is a word in dictionary. False if it is an unknown word span.
+    pub known: bool,
+    /// Frequency of the word in dictionary. It is 0 for unknown word.
+    pub frequency: u64,
+}
+
+/// Every vertex and edge of word graph of a text.
+/// 
+/// Each vertex is a byte offset in the text where a word may begin or end. Each white space separated
+/// chunk of text is a separated graph. Only vertices and edges that are reachable from the beginning
+/// of its chunk are in the lattice.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lattice<'a> {
+    /// The text of this lattice.
+    pub text: &'a str,
+    /// Byte offset of every vertex in ascending order.
+    pub vertices: Vec<usize>,
+    /// Every edge sorted by its start then its end.
+    pub edges: Vec<Edge<'a>>,
+}
+
+impl<'a> Lattice<'a> {
+    /// Get every edge that leave vertex at given byte offset.
+    pub fn edges_from(&self, offset: usize) -> &[Edge<'a>] {
+        let first = self.edges.partition_point(|e| e.start < offset);
+        let last = self.edges.partition_point(|e| e.start <= offset);
+        &self.edges[first..last]
+    }
+
+    /// Export this lattice to Graphviz DOT format.
+    /// 
+    /// Each vertex is named by its byte offset. A known word is a solid edge labeled by the word and
+    /// its frequency. An unknown word is a dashed edge labeled by the word.
+    /// For example, it can be rendered to image by `dot -Tsvg lattice.dot -o lattice.svg`.
+    pub fn to_dot(&self) -> String {
+        use std::fmt::Write;
+
+        /// Escape given text so it can be put inside double quoted DOT string.
+        fn escape(text: &str) -> String {
+            text.replace('\\', "\\\\").replace('"', "\\\"")
+        }
+
+        let mut dot = String::from("digraph lattice {\n    rankdir=LR;\n    node [shape=circle];\n");
+
+        for vertex in self.vertices.iter() {
+            writeln!(dot, "    {};", vertex).unwrap();
+        }
+
+        for edge in self.edges.iter() {
+            if edge.known {
+                writeln!(dot, "    {} -> {} [label=\"{} ({})\"];", edge.start, edge.end, escape(edge.text), edge.frequency).unwrap();
+            } else {
+                writeln!(dot, "    {} -> {} [label=\"{}\", style=dashed];", edge.start, edge.end, escape(edge.text)).unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests;
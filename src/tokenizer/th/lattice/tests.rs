@@ -0,0 +1,53 @@
+use crate::tokenizer::th::Tokenizer;
+
+#[test]
+fn test_lattice() {
+    let words = ["ตา", "กลม", "ตาก", "ลม", "ตากลม"];
+    let tokenizer = Tokenizer::from(&words[..]);
+    let lattice = tokenizer.lattice("ตากลม xyzลม");
+
+    assert_eq!(lattice.vertices, vec![0, 6, 9, 15, 16, 19, 25]);
+    assert_eq!(
+        lattice.edges.iter().map(|e| (e.text, e.start, e.end, e.known)).collect::<Vec<_>>(),
+        vec![
+            ("ตา", 0, 6, true),
+            ("ตาก", 0, 9, true),
+            ("ตากลม", 0, 15, true),
+            ("กลม", 6, 15, true),
+            ("ลม", 9, 15, true),
+            ("xyz", 16, 19, false),
+            ("ลม", 19, 25, true),
+        ]
+    );
+    assert_eq!(lattice.edges_from(0).len(), 3);
+    assert_eq!(lattice.edges_from(9), &lattice.edges[4..5]);
+    assert!(lattice.edges_from(3).is_empty());
+    lattice.edges.iter().for_each(|e| assert_eq!(&lattice.text[e.start..e.end], e.text));
+}
+
+#[test]
+fn test_unreachable_vertex() {
+    // "กข" and "ค" are never reachable from the beginning
+    let words = ["กขค", "ข", "ค"];
+    let tokenizer = Tokenizer::from(&words[..]);
+    let lattice = tokenizer.lattice("กขค");
+    assert_eq!(lattice.vertices, vec![0, 9]);
+    assert_eq!(lattice.edges.len(), 1);
+}
+
+#[test]
+fn test_to_dot() {
+    let tokenizer = Tokenizer::with_dict(crate::dict::tests::frequency_dict(&[("ตา", 7)]));
+    let dot = tokenizer.lattice("ตา\"x").to_dot();
+
+    assert_eq!(dot, "digraph lattice {
+    rankdir=LR;
+    node [shape=circle];
+    0;
+    6;
+    8;
+    0 -> 6 [label=\"ตา (7)\"];
+    6 -> 8 [label=\"\\\"x\", style=dashed];
+}
+");
+}
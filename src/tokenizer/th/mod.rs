@@ -7,121 +7,286 @@
 //! 
 //! It can handle some unknown words. It does so by minimizing number of characters 
 //! that need to be took off from the text until a known word is found. 
+//! 
+//! A word, either known or unknown, only begin and end on an edge of Thai character cluster.
+//! See [tcc](tcc/index.html).
+//! 
+//! When sub-word units are needed instead of dictionary words, [ClusterTokenizer](struct.ClusterTokenizer.html)
+//! and [SyllableTokenizer](struct.SyllableTokenizer.html) split text without any dictionary.
+//! 
+//! Alternatively, it can choose the most probable result according to frequency of each word
+//! in dictionary. See [Mode](enum.Mode.html).
+//! 
+//! White space and punctuation can be kept as tokens so the original text can be reconstructed
+//! from tokens. See [Separators](enum.Separators.html).
+//! 
+//! A long running service can swap in a new dictionary without restart. See [reload](reload/index.html).
 
-use crate::dict::{SizedNode, terminals_prefix};
-use super::MultiOwn;
-use super::{TreeOp, TreeNode};
+use crate::dict::{Frequency, Lookup, SizedDict};
+use super::{Parallelism, TokenKind, split_kinds, subslice_offset};
+use super::{NodeId, Tree, TreeOp};
+
+pub mod lattice;
+pub mod reload;
+pub mod tcc;
+
+pub use self::lattice::Lattice;
+pub use self::reload::{DictWatcher, ReloadableTokenizer};
+pub use self::tcc::{ClusterTokenizer, SyllableTokenizer};
 
 /// Extra metadata required to get a proper tokenization on Thai text.
-#[allow(dead_code)]
-struct LeafNode<T> {
+#[derive(Clone, Copy, Debug)]
+struct LeafNode {
     /// An actual leaf node on the tree.
-    node: T,
-    /// A bytes count of unknown characters on the branch of tokenization tree
-    unknown_bytes_count: usize
+    node: NodeId,
+    /// A score of the branch of tokenization tree, including number of unknown bytes on it
+    score: PathScore
 }
 
 /// Make a result tree contains all possible construct of `unit` combination.
-/// Caller typicall need to do `make_result_tree(&*dict.root, "SOME_TEXT_TO_PARSE", root_node)`
+/// Caller typicall need to do `make_result_tree(&dict, "SOME_TEXT_TO_PARSE", &mut tree, &[root_leaf], &mut leaves, log_total)`
 /// 
-/// Typically, `unit` is a word.
+/// Typically, `unit` is a word. Each branch from root to a leaf is a path through
+/// [word_graph](fn.word_graph.html) so every segmentation has the same words and unknown words
+/// as the ones that [maximal_matching](fn.maximal_matching.html) choose from.
+/// 
+/// The tree is built breadth-first without recursion so it can enumerate every segmentation of
+/// a long text as long as the number of segmentations fit in memory.
 /// 
 /// # Parameters
-/// - `nodes` - Set of root dictionary nodes. It's typical in [SizedDict](struct.SizedDict.html#field.root)
+/// - `nodes` - A dictionary to lookup for words. It's typically a [SizedDict](/tokenizer/dict/struct.SizedDict.html)
 /// - `value` - A string to be parsed by given dictionary.
-/// - `parent` - A [TreeNode](struct.TreeNode.html) that will be root node of all the parsed unit
-/// - `leaves` - A Vec contains all the possible leaves nodes.
-#[allow(dead_code)]
-fn make_result_tree<'a>(nodes: &[SizedNode], value: &'a str, parent: MultiOwn<TreeNode<&'a str>>, leaves: &mut Vec<LeafNode<MultiOwn<TreeNode<&'a str>>>>) {
-    #[cfg(not(feature="single-thread"))]
-    fn add_child<'a>(parent: &MultiOwn<TreeNode<&'a str>>, value: &'a str, upto: usize) -> MultiOwn<TreeNode<&'a str>> {
-        std::sync::Arc::clone(parent).add_child(&value[..upto])
-    }
-    #[cfg(feature="single-thread")]
-    fn add_child<'a>(parent: &MultiOwn<TreeNode<&'a str>>, value: &'a str, upto: usize) -> MultiOwn<TreeNode<&'a str>> {
-        std::rc::Rc::clone(parent).add_child(&value[..upto])
-    }
-    /// Consume a portion of value until either entire value is consumed or token(s) are found.
-    /// 
-    /// If it consumed entire value, it will add a leaf node into leaves vec.
-    /// 
-    /// If it consumed chunk of value, it will add a node to parent node, change pointer to parent node to new node,
-    /// change pointer to value to point to remaining slice, and add all potential known tokens to result vec.
-    /// 
-    /// In anycase, it will update consumed_bytes but not accumulated_unknown_bytes.
-    #[inline(always)]
-    fn consume_unknown<'a>(nodes: &[SizedNode], value: &mut &'a str, accumulated_unknown_bytes: usize, consumed_bytes: &mut usize, parent: &mut MultiOwn<TreeNode<&'a str>>, results: &mut Vec<usize>, leaves: &mut Vec<LeafNode<MultiOwn<TreeNode<&'a str>>>>) {
-        // Apply some algorithm to extract unknown word and repeatly re-evaluate if the remain
-        // from algorithm is a known word
-        let mut chars = value.chars(); // Take a chars iterator and consume all repeating chars
-
-        while let Some(c) = chars.next() {
-            *consumed_bytes += c.len_utf8();
-
-            terminals_prefix(nodes, value, *consumed_bytes, results);
-            
-            if results.len() > 0 {
-                // Found an offset where sub-sequence chars is a known word.
-                // Make a new node and make it parent of sun-sequence of valid words.
-                *parent = add_child(parent, value, *consumed_bytes);
-
-                // Shift value by consumed byte as the unknown word boundary is at consumed bytes.
-                *value = &value[*consumed_bytes..];
-
-                // Since the value to evaluate is a slice where start is shifted by consumed bytes,
-                // the results vec need to be subtracted by consumed bytes.
-                results.iter_mut().for_each(|val| *val -= *consumed_bytes);
-
-                // stop lookup as known word is found.
-                break;
+/// - `tree` - A [Tree](../struct.Tree.html) to add every parsed unit, along with a flag telling whether it is a known word, into.
+/// - `parents` - Leaves in `tree` to attach every parsed unit under, e.g. a root node or leaves of previous text.
+/// - `leaves` - A Vec contains all the possible leaves nodes. Score of each leaf include score of its parent.
+/// - `log_total` - Natural log of total frequency of `nodes`. It is used to calculate cost of each branch.
+fn make_result_tree<'a, D: Lookup + ?Sized>(nodes: &D, value: &'a str, tree: &mut Tree<(&'a str, bool)>, parents: &[LeafNode], leaves: &mut Vec<LeafNode>, log_total: f64) {
+    let graph = word_graph(nodes, value);
+    // 0 is offset of remain value and score of parent is accumulated score of given tree branch
+    let mut eval_queue: std::collections::VecDeque<_> = parents.iter().map(|parent| (0, parent.node, parent.score)).collect();
+
+    while let Some((offset, parent, accumulated_score)) = eval_queue.pop_front() {
+        // Each branch shall have it own remain value to be evaluate, node parent, and accumulated score.
+        for (end, freq, known) in graph[offset].iter() {
+            let node = tree.add_child(parent, (&value[offset..*end], *known));
+            let score = accumulated_score.join(&PathScore::of_word(value, offset, *end, *freq, *known, log_total));
+
+            if *end < value.len() {
+                // more value remain to try
+                eval_queue.push_back((*end, node, score));
+            } else {
+                // no more value, add matched terminal to `leaves` vec
+                leaves.push(LeafNode {node, score});
             }
         }
+    }
+}
 
-        if results.len() == 0 {
-            // Entire value is consumed and no known word found.
-            // It mean there's unknown word trailing or entire value is an unknown word.
-            // No need to lookup more. Simply make entire value as a single leaf node.
-            let node = add_child(parent, value, *consumed_bytes);
-            leaves.push(LeafNode {
-                node,
-                unknown_bytes_count: accumulated_unknown_bytes + *consumed_bytes,
-            });
+/// A rule that [maximal_matching](fn.maximal_matching.html) use to choose the best path among
+/// many possible segmentations.
+/// 
+/// Every rule end with preferring a path that has longer first word when everything else is equal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Policy {
+    /// Choose a path with least number of words. If there's many, choose one that has least
+    /// number of unknown bytes.
+    #[default]
+    FewestTokens,
+    /// Choose a path with least number of unknown bytes. If there's many, choose one that has
+    /// least number of words.
+    FewestUnknown,
+    /// Choose a path with least number of words. If there's many, choose one whose first word is
+    /// longest, then whose second word is longest, and so on.
+    LongestFirstWord,
+    /// Choose a path with least number of words. If there's many, choose one whose product of
+    /// word frequency is highest. Unknown word and word with frequency 0 count as frequency 1.
+    HighestFrequency,
+}
+
+/// A summary of a path from a vertex to the end of text.
+#[derive(Clone, Copy, Debug, Default)]
+struct PathScore {
+    /// Number of words on the path
+    tokens: usize,
+    /// Number of bytes of unknown words on the path
+    unknown_bytes: usize,
+    /// Sum of natural log of frequency of every word on the path
+    log_frequency: f64,
+    /// Sum of cost of every word on the path by unigram model. See [unigram](fn.unigram.html).
+    cost: f64,
+}
+
+impl PathScore {
+    /// A score of a path that has only one word from `offset` to `end` of `text`.
+    /// An unknown word has no frequency and cost more than any known word. See [unigram](fn.unigram.html).
+    fn of_word(text: &str, offset: usize, end: usize, freq: u64, known: bool, log_total: f64) -> PathScore {
+        if known {
+            let log_frequency = (freq.max(1) as f64).ln();
+            PathScore {tokens: 1, unknown_bytes: 0, log_frequency, cost: log_total - log_frequency}
+        } else {
+            let chars = text[offset..end].chars().count() as f64;
+            PathScore {tokens: 1, unknown_bytes: end - offset, log_frequency: 0.0, cost: chars * (log_total + UNKNOWN_CHAR_PENALTY)}
         }
     }
 
-    let mut eval_queue = std::collections::VecDeque::new();
-    eval_queue.push_back((value, parent, 0)); // 0 is accumulated unknown bytes of given tree branch
-    let mut results = Vec::new();
+    /// A score of a path that is made by following this path with `other` path.
+    fn join(&self, other: &PathScore) -> PathScore {
+        PathScore {
+            tokens: self.tokens + other.tokens,
+            unknown_bytes: self.unknown_bytes + other.unknown_bytes,
+            log_frequency: self.log_frequency + other.log_frequency,
+            cost: self.cost + other.cost,
+        }
+    }
+}
 
-    while let Some((mut value, mut parent, accumulated_unknown_bytes)) = eval_queue.pop_front() {
-        // Each branch shall have it own remain value to be evaluate, node parent, and accumulated unknown bytes.
-        results.clear();
-        terminals_prefix(nodes, value, 0, &mut results);
+/// An order of paths in word graph.
+#[derive(Clone, Copy, Debug)]
+enum Ranking {
+    /// Order by given policy
+    Policy(Policy),
+    /// Order by cost of unigram model, lower is better. If it is equal, longer first word is better.
+    Cost,
+}
+
+impl Ranking {
+    /// Compare two paths that leave the same vertex. Each path is a score and an offset of the end of its first word.
+    /// The path that is `Less` is better.
+    fn compare(self, a: (&PathScore, usize), b: (&PathScore, usize)) -> std::cmp::Ordering {
+        match self {
+            Ranking::Policy(policy) => policy.compare(a, b),
+            Ranking::Cost => a.0.cost.total_cmp(&b.0.cost).then(b.1.cmp(&a.1))
+        }
+    }
+}
+
+/// A link from a vertex to next vertex on one of the best paths from the vertex.
+#[derive(Clone, Copy, Debug)]
+struct PathLink {
+    /// Score of the path from the vertex to the end of text
+    score: PathScore,
+    /// Offset of next vertex. It is also the end of a word from the vertex.
+    end: usize,
+    /// A flag telling whether the word is a known word
+    known: bool,
+    /// Index of the rest of the path in the best paths of next vertex
+    rank: usize,
+}
 
-        // A prefix bytes that was unknown word which need to be consumed in order to reach known word.
-        let mut consumed_bytes = 0;
+impl Policy {
+    /// Compare two paths that leave the same vertex. Each path is a score and an offset of the end of its first word.
+    /// The path that is `Less` is better.
+    fn compare(self, a: (&PathScore, usize), b: (&PathScore, usize)) -> std::cmp::Ordering {
+        let ((a, a_end), (b, b_end)) = (a, b);
+        // longer first word is better
+        let first_word = b_end.cmp(&a_end);
 
-        // no match for any entry in dictionary
-        if results.len() == 0 && value.len() > 0 {
-            consume_unknown(nodes, &mut value, accumulated_unknown_bytes, &mut consumed_bytes, &mut parent, &mut results, leaves);
+        match self {
+            Policy::FewestTokens => a.tokens.cmp(&b.tokens).then(a.unknown_bytes.cmp(&b.unknown_bytes)),
+            Policy::FewestUnknown => a.unknown_bytes.cmp(&b.unknown_bytes).then(a.tokens.cmp(&b.tokens)),
+            Policy::LongestFirstWord => a.tokens.cmp(&b.tokens),
+            Policy::HighestFrequency => a.tokens.cmp(&b.tokens)
+                                            .then(b.log_frequency.total_cmp(&a.log_frequency))
+                                            .then(a.unknown_bytes.cmp(&b.unknown_bytes)),
+        }.then(first_word)
+    }
+}
+
+/// Build a word graph of given text.
+/// 
+/// Each cluster edge of text is a vertex and each known word is an edge of word graph. If there's no known
+/// word from a vertex, an unknown word edge goes from that vertex to the nearest vertex that a known word begin.
+/// # Parameters
+/// - `dict` - A dictionary such as [dict::SizedDict](/tokenizer/dict/struct.SizedDict.html).
+/// - `text` - A slice of string to build a graph.
+/// # Return
+/// A vec of `text.len() + 1` elements. Each element is edges that leave the vertex at that byte offset.
+/// Each edge is an offset of the end of the word, its frequency and a flag telling whether it is a known word.
+/// An unknown word has frequency 0. An offset that is not a vertex and the end of text have no edge.
+fn word_graph<D: Lookup + ?Sized>(dict: &D, text: &str) -> Vec<Vec<(usize, u64, bool)>> {
+    // Every word shall begin and end on a Thai character cluster edge
+    let edges = tcc::boundaries(text);
+    let mut graph = vec![Vec::new(); text.len() + 1];
+    // The nearest vertex after current vertex that at least one known word begin.
+    let mut next_known = text.len();
+    // Reusable vec of end offset and frequency of known words that begin at current vertex.
+    let mut words = Vec::new();
+
+    for offset in (0..text.len()).rev().filter(|offset| edges[*offset]) {
+        words.clear();
+        dict.frequencies_prefix(text, offset, &mut words);
+        // ignore empty word and word that end inside cluster
+        words.retain(|(end, _)| *end > offset && edges[*end]);
+
+        if words.is_empty() {
+            // No known word so unknown word span until next known word
+            graph[offset].push((next_known, 0, false));
+        } else {
+            next_known = offset;
+            graph[offset].extend(words.iter().map(|(end, freq)| (*end, *freq, true)));
         }
+    }
 
-        let new_accumulated_unknown_bytes = accumulated_unknown_bytes + consumed_bytes;
-        
-        // On each result, it is a known word offset
-        results.iter().for_each(|offset| {
-            let node = add_child(&parent, value, *offset);
-            let remain = &value[(*offset)..];
+    graph
+}
 
-            if remain.len() > 0 {
-                // more value remain to try
-                eval_queue.push_back((remain, node, new_accumulated_unknown_bytes));
-            } else {
-                // no more value, add matched terminal to `leaves` vec
-                leaves.push(LeafNode {node, unknown_bytes_count: new_accumulated_unknown_bytes});
-            }
-        });
+/// Find `k` best paths through [word_graph](fn.word_graph.html) of given text.
+/// 
+/// Vertices are visited from the end of text backward. Each vertex keep only `k` best paths to the end of text
+/// according to given ranking. Thus the best paths from the first vertex are the result.
+/// # Parameters
+/// - `dict` - A dictionary such as [dict::SizedDict](/tokenizer/dict/struct.SizedDict.html).
+/// - `text` - A slice of string to be tokenized.
+/// - `k` - Maximum number of paths to return.
+/// - `ranking` - An order of paths.
+/// - `log_total` - Natural log of total frequency of `dict`. It is used to calculate cost of each path.
+/// # Return
+/// A vec of score of each path along with words on the path sorted from the best one. Each word come with
+/// a flag telling whether the word is a known word. An empty text has exactly one path without any word.
+fn best_paths<'a, D: Lookup + ?Sized>(dict: &D, text: &'a str, k: usize, ranking: Ranking, log_total: f64) -> Vec<(PathScore, Vec<(&'a str, bool)>)> {
+    let graph = word_graph(dict, text);
+    // Best paths from each vertex to the end of text.
+    let mut best: Vec<Vec<PathLink>> = vec![Vec::new(); text.len() + 1];
+    best[text.len()].push(PathLink {score: PathScore::default(), end: text.len(), known: true, rank: 0});
+
+    for offset in (0..text.len()).rev() {
+        let mut candidates = Vec::new();
+
+        for (end, freq, known) in graph[offset].iter() {
+            let word = PathScore::of_word(text, offset, *end, *freq, *known, log_total);
+            candidates.extend(extend_paths(&best[*end], *end, *known, word));
+        }
+
+        // Stable sort keep the order of paths that share the same first word
+        candidates.sort_by(|a, b| ranking.compare((&a.score, a.end), (&b.score, b.end)));
+        candidates.truncate(k);
+        best[offset] = candidates;
     }
+
+    (0..best[0].len()).map(|rank| {
+        let score = best[0][rank].score;
+        let mut path = Vec::with_capacity(score.tokens);
+        let (mut offset, mut rank) = (0, rank);
+
+        while offset < text.len() {
+            let link = best[offset][rank];
+            path.push((&text[offset..link.end], link.known));
+            offset = link.end;
+            rank = link.rank;
+        }
+
+        (score, path)
+    }).collect()
+}
+
+/// Prepend a word that end at `end` to every given path that begin at `end`.
+fn extend_paths(paths: &[PathLink], end: usize, known: bool, word: PathScore) -> Vec<PathLink> {
+    paths.iter().enumerate().map(|(rank, link)| PathLink {
+        score: word.join(&link.score),
+        end,
+        known,
+        rank
+    }).collect()
 }
 
 /// Maximal matching algorithm with unknown word support.
@@ -130,208 +295,549 @@ fn make_result_tree<'a>(nodes: &[SizedNode], value: &'a str, parent: MultiOwn<Tr
 /// See this [Wikipedia page](https://en.wikipedia.org/wiki/Matching_(graph_theory)#Maximal_matchings) 
 /// for brief explanation of the algorithm.
 /// 
-/// It take a dictionary in form of &[SizedNode] and a text to be tokenized.
+/// It take a dictionary that implement [dict::Lookup](/tokenizer/dict/trait.Lookup.html) and a text to be tokenized.
+/// It is the same path as the best one found by [best_paths](fn.best_paths.html) according to given
+/// [Policy](enum.Policy.html). Only the best path from each vertex is kept so nothing is allocated per vertex.
 /// # Parameters
-/// - `dict` - A slice of [dict::SizedNode](/tokenizer/dict/struct.SizedNode.html) which
-/// can be obtain from `root` field of [dict::SizedDict](/tokenizer/dict/struct.SizedDict.html).
+/// - `dict` - A dictionary such as [dict::SizedDict](/tokenizer/dict/struct.SizedDict.html).
 /// - `text` - A slice of string to be tokenized.
+/// - `policy` - A rule to choose the best path.
 /// # Return
-/// A vec contains slice of tokenized word.
-fn maximal_matching<'a>(dict: &[SizedNode], text: &'a str) -> Vec<&'a str> {
-    /// There's three possible states in one vertex
-    #[derive(Clone)]
-    enum VertexState {
-        /// A vertex that nobody visit yet
-        None,
-        /// A vertex that is recognized in advance while attempting to isloate unknown word
-        Cache(Vec<usize>),
-        /// A vertex that is visited by breadth-first-search strategy
-        Some(Vec<usize>)
-    }
-
-    impl Default for VertexState {
-        /// By default, `VertexState` is `None`
-        fn default() -> VertexState {
-            VertexState::None
+/// A vec contains slice of tokenized word along with a flag telling whether the word is
+/// a known word. It is `false` if the word is an unknown word isolated by this algorithm.
+fn maximal_matching<'a, D: Lookup + ?Sized>(dict: &D, text: &'a str, policy: Policy) -> Vec<(&'a str, bool)> {
+    let ranking = Ranking::Policy(policy);
+    let edges = tcc::boundaries(text);
+    // The best path from each vertex to the end of text. It is a score of the path, an offset of
+    // next vertex and a flag telling whether the word to next vertex is a known word.
+    let mut best: Vec<Option<(PathScore, usize, bool)>> = vec![None; text.len() + 1];
+    best[text.len()] = Some((PathScore::default(), text.len(), true));
+    // The nearest vertex after current vertex that at least one known word begin.
+    let mut next_known = text.len();
+    // Reusable vec of end offset and frequency of known words that begin at current vertex.
+    let mut words = Vec::new();
+
+    // The same edges as word_graph but each one is scored as soon as it is found
+    for offset in (0..text.len()).rev().filter(|offset| edges[*offset]) {
+        words.clear();
+        dict.frequencies_prefix(text, offset, &mut words);
+        // ignore empty word and word that end inside cluster
+        words.retain(|(end, _)| *end > offset && edges[*end]);
+
+        let mut best_here: Option<(PathScore, usize, bool)> = None;
+        // Every word end on a vertex after this one so the best path from there is already known
+        let mut consider = |end: usize, freq: u64, known: bool| {
+            let (rest, _, _) = best[end].unwrap();
+            let score = PathScore::of_word(text, offset, end, freq, known, 0.0).join(&rest);
+
+            if best_here.is_none_or(|(best_score, best_end, _)| ranking.compare((&score, end), (&best_score, best_end)).is_lt()) {
+                best_here = Some((score, end, known));
+            }
+        };
+
+        if words.is_empty() {
+            // No known word so unknown word span until next known word
+            consider(next_known, 0, false);
+        } else {
+            words.iter().for_each(|(end, freq)| consider(*end, *freq, true));
+            next_known = offset;
         }
+
+        best[offset] = best_here;
     }
 
-    impl VertexState {
-        /// Take current value out of this `VertexState` and leave `None` in place.
-        /// If current state is `None`, it return `Option::None`
-        pub fn take(&mut self) -> Option<Vec<usize>> {
-            let value = std::mem::take(self);
-            match value {
-                VertexState::None => None,
-                VertexState::Cache(v) | VertexState::Some(v) => {
-                    Some(v)
-                },
+    let mut path = Vec::new();
+    let mut offset = 0;
+
+    while offset < text.len() {
+        let (_, end, known) = best[offset].expect("Every vertex has a path to the end of text");
+        path.push((&text[offset..end], known));
+        offset = end;
+    }
+
+    path
+}
+
+/// A cost of each unknown char on top of a cost of the rarest possible word.
+/// 
+/// It make every path that cover a text with known words cheaper than a path that
+/// has more unknown chars.
+const UNKNOWN_CHAR_PENALTY: f64 = 10.0;
+
+/// Unigram language model segmentation decoded by Viterbi algorithm.
+/// 
+/// Each vertex is a cluster edge of text and each known word is an edge, like
+/// [word_graph](fn.word_graph.html). Unlike that graph, every cluster is also an unknown edge of
+/// its own, even where a known word begin, so a path may skip a known word that cost too much.
+/// Each known word cost `ln(total_frequency) - ln(frequency)`. A word with frequency 0 is treated as
+/// frequency 1. Each unknown char cost `ln(total_frequency) + UNKNOWN_CHAR_PENALTY`, which is more
+/// than any known word. The path with least total cost is chosen.
+/// 
+/// # Parameters
+/// - `dict` - A dictionary such as [dict::SizedDict](/tokenizer/dict/struct.SizedDict.html).
+/// - `text` - A slice of string to be tokenized.
+/// - `total_frequency` - Sum of frequency of every word in `dict`.
+/// # Return
+/// The same as [maximal_matching](fn.maximal_matching.html). Consecutive unknown chars
+/// are merged into one unknown word.
+fn unigram<'a, D: Lookup + ?Sized>(dict: &D, text: &'a str, total_frequency: u64) -> Vec<(&'a str, bool)> {
+    let log_total = (total_frequency.max(1) as f64).ln();
+    let unknown_cost = log_total + UNKNOWN_CHAR_PENALTY;
+    let edges = tcc::boundaries(text);
+
+    // Best path that end at each byte offset. It is a cost, an offset of previous vertex,
+    // and a flag telling whether the edge from previous vertex is a known word.
+    let mut best: Vec<Option<(f64, usize, bool)>> = vec![None; text.len() + 1];
+    best[0] = Some((0.0, 0, true));
+    let mut words = Vec::new();
+
+    for offset in (0..text.len()).filter(|offset| edges[*offset]) {
+        // Every cluster edge is reachable by unknown edge
+        let cost = best[offset].unwrap().0;
+
+        words.clear();
+        dict.frequencies_prefix(text, offset, &mut words);
+
+        let cluster_end = offset + tcc::cluster_len(text, offset);
+        let unknown_edge = (cluster_end, cost + unknown_cost * text[offset..cluster_end].chars().count() as f64, false);
+        let known_edges = words.iter()
+                                .filter(|(end, _)| *end > offset && edges[*end]) // ignore empty word and word that end inside cluster
+                                .map(|(end, freq)| (*end, cost + log_total - ((*freq).max(1) as f64).ln(), true));
+
+        for (end, cost, known) in known_edges.chain(std::iter::once(unknown_edge)) {
+            if best[end].is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                best[end] = Some((cost, offset, known));
             }
         }
     }
 
-    /// Take as least necessary bytes as needed until first known word is found.
-    /// It need a dictionary to identify a known word. It will increment an offset by one character
-    /// until there's a prefix match to a dictionary entry.
-    /// For example, if text is "abcdef" and "ab" is unknown word and "cd" and "cde" is known word in dic
-    /// then this function will put 3, and 4 in `results` vec and return 2 which is unknown word boundary.
-    /// 
-    /// # Parameters
-    /// - `nodes` - A slice of [dict::SizedNode](/tokenizer/dict/struct.SizedNode.html) which
-    /// can be obtain from `root` field of [dict::SizedDict](/tokenizer/dict/struct.SizedDict.html).
-    /// - `value` - A string slice to find an offset of unknown words.
-    /// - `offset` - A position to start isolate an unknown word.
-    /// - `results` - A vec which will store known words that come after the isolated unknown word.
-    /// # Return
-    /// It return an offset boundary of unknown word.
-    /// For example, if text is "abcdef" and "cd" is the only unknown word and offset is 2,
-    /// it will return 4. Caller can directly took slice from `&value[2..4]` to obtains that "cd"
-    fn consume_unknown<'a>(nodes: &[SizedNode], value: &str, offset: usize, results: &mut Vec<usize>) -> usize {
-        // Apply some algorithm to extract unknown word and repeatly re-evaluate if the remain
-        // from algorithm is a known word
-        let mut consumed_bytes = offset;
-        let mut chars = value[consumed_bytes..].chars(); // Take a chars iterator and consume all repeating chars
-
-        while let Some(c) = chars.next() {
-            consumed_bytes += c.len_utf8();
-
-            terminals_prefix(nodes, value, consumed_bytes, results);
-            
-            if results.len() > 0 {
-                // stop lookup as known word is found.
-                break;
+    let mut result = std::collections::VecDeque::new();
+    let mut end = text.len();
+    // An end of unknown word that is being merged
+    let mut unknown_end = None;
+
+    while end > 0 {
+        let (_, start, known) = best[end].unwrap();
+
+        if known {
+            if let Some(unknown_end) = unknown_end.take() {
+                result.push_front((&text[end..unknown_end], false));
             }
+            result.push_front((&text[start..end], true));
+        } else if unknown_end.is_none() {
+            unknown_end = Some(end);
         }
 
-        consumed_bytes
+        end = start;
     }
 
-    // 2D dynamic vec to simulate word graph
-    let mut vertices = vec![VertexState::None; text.len()];
-    // `branches` is reusable vec to temporarily hold possible branch from any particular vertex.
-    let mut branches = Vec::with_capacity(text.len());
-    // `queue` is a processing queue of vertex to be processed.
-    // The vertex being pushed to this queue shall make a graph traversal "breadth-first"
-    let mut queue = Vec::with_capacity(text.len() / 2);
+    if let Some(unknown_end) = unknown_end {
+        result.push_front((&text[..unknown_end], false));
+    }
 
-    queue.push([0, 0, 0]); // previous pointer, current vertex index, unknown bytes count
+    result.into()
+}
 
-    // Assuming text has at least 2 chars per word
-    let mut result = std::collections::VecDeque::with_capacity(text.len() / 2); 
+/// An algorithm that Thai [Tokenizer](struct.Tokenizer.html) use to choose one of possible segmentations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Choose a segmentation that has least number of words.
+    /// See [maximal_matching](fn.maximal_matching.html) and [Policy](enum.Policy.html).
+    #[default]
+    MaximalMatching,
+    /// Choose the most probable segmentation according to frequency of each word in dictionary.
+    /// See [unigram](fn.unigram.html).
+    Unigram,
+}
 
-    let mut i = 0;
-    let mut not_ended = true;
+/// Which separators a Thai [Tokenizer](struct.Tokenizer.html) emit as tokens.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Separators {
+    /// White space is discarded. Punctuation that is not part of any dictionary word is
+    /// still a token of its own.
+    #[default]
+    Discard,
+    /// Every run of white space is a token of kind `Whitespace`. Concatenating all tokens
+    /// reproduce the original text.
+    Whitespace,
+    /// Like `Whitespace` but every run of punctuation is also a separator. Text is split at
+    /// punctuation before dictionary lookup so punctuation is never a part of any word, even if
+    /// dictionary has such word, e.g. `ฯลฯ`. A `.` or `,` between digits is part of a number.
+    WhitespaceAndPunctuation,
+}
 
-    while not_ended {
-        // Retreive next offset
-        let [_, offset, unknown_len] = queue[i];
-        branches.clear();
+/// Dictionary based Thai text tokenizer
+/// 
+/// By default, it uses [SizedDict](/tokenizer/dict/struct.SizedDict.html) as dictionary.
+/// Other dictionary that implement [Lookup](/tokenizer/dict/trait.Lookup.html) can be used
+/// via [with_dict](struct.Tokenizer.html#method.with_dict).
+/// 
+/// It uses [Mode::MaximalMatching](enum.Mode.html#variant.MaximalMatching) by default.
+/// Other algorithm can be chosen by [with_mode](struct.Tokenizer.html#method.with_mode).
+/// 
+/// White space is discarded by default. It can be kept by [with_separators](struct.Tokenizer.html#method.with_separators).
+/// 
+/// Chunks of text are tokenized in parallel according to [Parallelism::default](/tokenizer/enum.Parallelism.html).
+/// It can be changed by [with_parallelism](struct.Tokenizer.html#method.with_parallelism).
+pub struct Tokenizer<D = SizedDict> {
+    dict: D,
+    mode: Mode,
+    policy: Policy,
+    separators: Separators,
+    parallelism: Parallelism,
+    /// Minimum number of documents each thread take at a time in [tokenize_batch](struct.Tokenizer.html#method.tokenize_batch).
+    batch_min_len: usize,
+    /// Sum of frequency of every word in `dict`.
+    total_frequency: u64,
+}
 
-        match vertices[offset] {
-            VertexState::None => {
-                // Find next prefix from offset
-                terminals_prefix(dict, text, offset, &mut branches);
+/// One of possible segmentations of a text. It is a result of [n_best](struct.Tokenizer.html#method.n_best).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segmentation<'a> {
+    /// Tokens of this segmentation. Like [tokenize_spans](/tokenizer/trait.SpanTokenizer.html#tymethod.tokenize_spans),
+    /// an unknown word is classified by [TokenKind::of](/tokenizer/enum.TokenKind.html#method.of).
+    pub tokens: Vec<crate::tokenizer::Token<'a>>,
+    /// Number of words on the path through word graph.
+    pub word_count: usize,
+    /// Number of bytes of unknown words.
+    pub unknown_bytes: usize,
+    /// Negative cost of this segmentation, higher is better. Each known word cost
+    /// `ln(total_frequency) - ln(frequency)`, treating frequency 0 as 1, and each char of unknown
+    /// words cost `ln(total_frequency) + 10`. Without unknown words, it is the natural log of probability
+    /// of this segmentation by unigram model. See [unigram](fn.unigram.html).
+    pub score: f64,
+}
 
-                // create state of vertex which is Vec that contains offset to vertex
-                let mut vertex = Vec::with_capacity(branches.len());
+impl<'a> Segmentation<'a> {
+    /// Make a segmentation of `text` from words on a path through word graph and the score of the path.
+    fn new(text: &'a str, score: PathScore, words: Vec<(&'a str, bool)>) -> Segmentation<'a> {
+        use crate::tokenizer::make_tokens;
 
-                for v in branches.iter() {
-                    vertex.push(*v); // add next offset to vertex state
-                    queue.push([i, *v, unknown_len]);
+        let ranges = words_kinds(words).into_iter().map(|(token, kind)| {
+            let start = subslice_offset(text, token);
+            (start, start + token.len(), kind)
+        });
 
-                    if *v >= text.len() {
-                        not_ended = false;
-                        break;
-                    }
-                }
+        Segmentation {
+            tokens: make_tokens(text, ranges),
+            word_count: score.tokens,
+            unknown_bytes: score.unknown_bytes,
+            score: -score.cost
+        }
+    }
+}
 
-                if branches.len() > 0 {
-                    // known word case
-                    vertices[offset] = VertexState::Some(vertex);
-                } else {
-                    // No known word match so not even single branch is return
-                    let cur_unknown_length = consume_unknown(dict, text, offset, &mut branches);
-                    queue.push([i, cur_unknown_length, unknown_len + cur_unknown_length - offset]); // Identified unknown word boundary
-                    
-                    vertices[offset] = VertexState::Some(vec![cur_unknown_length]);
-
-                    if cur_unknown_length >= text.len() { // Unknown word is trailing in text
-                        break; // No need to do any futher processing
-                    }
-
-                    // All the returned branches are 1 step ahead of other branch so don't push it to queue yet
-                    // or it will break breadth-first strategy
-                    let mut peeked = branches.clone(); 
-                    peeked.shrink_to_fit(); // The branch size shall never changed. 
-                    vertices[cur_unknown_length] = VertexState::Cache(peeked); 
-                }
-            },
-            VertexState::Cache(_) => {
-                // Reach the peeked branches. Push all these branch into processing queue.
-                let nexts = vertices[offset].take();
-
-                if let Some(nexts) = nexts {
-                    // attempt to add each vertex to processing queue.
-                    for v in nexts.iter() {
-                        queue.push([i, *v, unknown_len]);
-
-                        if *v >= text.len() {
-                            // There is a vertex that already reach last char of text.
-                            not_ended = false;
-                            break
-                        }
-                    }
-                    vertices[offset] = VertexState::Some(nexts); // Change state of vertex to Some
-                }
-            },
-            VertexState::Some(_) => {
-                // We need to update the link back if vertex count is equals and unknown word count is lower. 
-                // So that it pick the path with least unknown word.
-                // Since the result is construct based on queue and best result is a last vertex in queue,
-                // it might point to a path that has more longer unknown token.
-            }
+impl<D: Lookup> Tokenizer<D> {
+    /// Construct a Thai tokenizer using given dictionary.
+    pub fn with_dict(dict: D) -> Tokenizer<D> {
+        Tokenizer {
+            mode: Mode::default(),
+            policy: Policy::default(),
+            separators: Separators::default(),
+            parallelism: Parallelism::default(),
+            batch_min_len: 1,
+            total_frequency: dict.total_frequency(),
+            dict
+        }
+    }
+
+    /// Choose an algorithm that this tokenizer use.
+    /// 
+    /// For example, to choose the most probable segmentation by word frequency:
+    /// ```no_run
+    /// use tokenizer::{Tokenizer, dict::{Dict, SizedDict}, th};
+    /// let dict = SizedDict::from(Dict::load_tsv("path/to/dictionary.tsv")?);
+    /// let tokenizer = th::Tokenizer::with_dict(dict).with_mode(th::Mode::Unigram);
+    /// assert_eq!(tokenizer.tokenize("กรรมการ"), vec!["กรรมการ"]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn with_mode(mut self, mode: Mode) -> Tokenizer<D> {
+        self.mode = mode;
+        self
+    }
+
+    /// Get an algorithm that this tokenizer use.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Choose a rule to pick the best segmentation when [Mode::MaximalMatching](enum.Mode.html#variant.MaximalMatching)
+    /// found many possible segmentations. It has no effect on other mode.
+    pub fn with_policy(mut self, policy: Policy) -> Tokenizer<D> {
+        self.policy = policy;
+        self
+    }
+
+    /// Get a rule to pick the best segmentation of this tokenizer.
+    pub fn policy(&self) -> Policy {
+        self.policy
+    }
+
+    /// Choose which separators are emitted as tokens.
+    /// 
+    /// For example, to keep white space so the original text can be reconstructed from tokens:
+    /// ```
+    /// use tokenizer::{Tokenizer, th};
+    /// let tokenizer = th::Tokenizer::from(&["ภาษา", "ไทย"][..]).with_separators(th::Separators::Whitespace);
+    /// let tokens = tokenizer.tokenize("ภาษาไทย  ภาษา");
+    /// assert_eq!(tokens, vec!["ภาษา", "ไทย", "  ", "ภาษา"]);
+    /// assert_eq!(tokens.concat(), "ภาษาไทย  ภาษา");
+    /// ```
+    pub fn with_separators(mut self, separators: Separators) -> Tokenizer<D> {
+        self.separators = separators;
+        self
+    }
+
+    /// Get which separators are emitted as tokens.
+    pub fn separators(&self) -> Separators {
+        self.separators
+    }
+
+    /// Choose how this tokenizer spread its work across threads.
+    /// 
+    /// For example, to tokenize on a dedicated thread pool:
+    /// ```no_run
+    /// # #[cfg(feature="multi-thread")] {
+    /// use std::sync::Arc;
+    /// use tokenizer::{Parallelism, th};
+    /// let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+    /// let tokenizer = th::Tokenizer::new("path/to/dictionary.txt").unwrap()
+    ///     .with_parallelism(Parallelism::Pool(Arc::new(pool)));
+    /// # }
+    /// ```
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Tokenizer<D> {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Get how this tokenizer spread its work across threads.
+    pub fn parallelism(&self) -> &Parallelism {
+        &self.parallelism
+    }
+
+    /// Set minimum number of documents each thread take at a time in
+    /// [tokenize_batch](struct.Tokenizer.html#method.tokenize_batch). Default is 1.
+    /// 
+    /// For many short documents, a larger value reduce overhead of distributing work to threads.
+    pub fn with_batch_min_len(mut self, min_len: usize) -> Tokenizer<D> {
+        self.batch_min_len = min_len.max(1);
+        self
+    }
+
+    /// Get minimum number of documents each thread take at a time in
+    /// [tokenize_batch](struct.Tokenizer.html#method.tokenize_batch).
+    pub fn batch_min_len(&self) -> usize {
+        self.batch_min_len
+    }
+
+    /// Get a dictionary of this tokenizer.
+    /// 
+    /// For example, to check whether a word is in dictionary:
+    /// ```no_run
+    /// use tokenizer::th;
+    /// let tokenizer = th::Tokenizer::new("path/to/dictionary.txt")?;
+    /// assert!(tokenizer.dict().contains("ภาษาไทย"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn dict(&self) -> &D {
+        &self.dict
+    }
+
+    /// Construct a tokenizer with the same configuration as this one but with given dictionary.
+    pub(crate) fn with_same_config(&self, dict: D) -> Tokenizer<D> {
+        Tokenizer {
+            mode: self.mode,
+            policy: self.policy,
+            separators: self.separators,
+            parallelism: self.parallelism.clone(),
+            batch_min_len: self.batch_min_len,
+            total_frequency: dict.total_frequency(),
+            dict
+        }
+    }
+
+    /// Return up to `k` best segmentations of given text, the best one first.
+    /// 
+    /// Text is split by white space first. Each chunk is segmented through the same word graph as
+    /// [maximal_matching](fn.maximal_matching.html). The segmentations are ordered by current
+    /// [Policy](enum.Policy.html). With [Mode::Unigram](enum.Mode.html#variant.Unigram), they are
+    /// ordered by their score instead. A word graph has no unknown word edge where a known word begin
+    /// so the best one may be different from [tokenize](/tokenizer/trait.Tokenizer.html#tymethod.tokenize)
+    /// in that mode.
+    /// 
+    /// For example, to expand a search query:
+    /// ```no_run
+    /// use tokenizer::th;
+    /// let tokenizer = th::Tokenizer::new("path/to/dictionary.txt")?;
+    /// for segmentation in tokenizer.n_best("ตากลม", 3) {
+    ///     println!("{:?} {}", segmentation.tokens.iter().map(|t| t.text).collect::<Vec<_>>(), segmentation.score);
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn n_best<'a>(&self, text: &'a str, k: usize) -> Vec<Segmentation<'a>> {
+        if k == 0 {
+            return Vec::new()
         }
 
-        i += 1;
-    }
-
-    let last_queue = queue.len() - 1;
-    // Prune remain queue to see if there's any last vertex candidate with lesser unknown word.
-    // Check only up to vertex before last element as last element is currently comparator vertex
-    while i < queue.len() - 1 {
-        let [_, offset, unknown_bytes] = queue[i];
-        match vertices[offset] {
-            VertexState::None | VertexState::Cache(_) => {},
-            VertexState::Some(ref vs) => {
-                if vs.iter().any(|v| {*v >= text.len()}) && unknown_bytes < queue[last_queue][2] {
-                    // There's at least one edge point to last node with lesser unknown_bytes.
-                    // Redirect last vertex reverse link to current vertex instead as it is new best vertex.
-                    queue[last_queue][0] = i;
+        let ranking = match self.mode {
+            Mode::MaximalMatching => Ranking::Policy(self.policy),
+            Mode::Unigram => Ranking::Cost,
+        };
+        let log_total = (self.total_frequency.max(1) as f64).ln();
+        // Best combinations of paths of every chunk so far.
+        let mut paths: Vec<(PathScore, Vec<(&'a str, bool)>)> = vec![(PathScore::default(), Vec::new())];
+
+        for chunk in text.split_whitespace() {
+            let chunk_paths = best_paths(&self.dict, chunk, k, ranking, log_total);
+            let mut combined = Vec::with_capacity(paths.len() * chunk_paths.len());
+
+            for (score, words) in paths.iter() {
+                for (chunk_score, chunk_words) in chunk_paths.iter() {
+                    let mut words = words.clone();
+                    words.extend_from_slice(chunk_words);
+                    combined.push((score.join(chunk_score), words));
                 }
             }
+
+            // Every combination has the same first word offset so stable sort keep the order of previous chunks
+            combined.sort_by(|(a, _), (b, _)| ranking.compare((a, 0), (b, 0)));
+            combined.truncate(k);
+            paths = combined;
         }
-        i += 1;
+
+        paths.into_iter().map(|(score, words)| Segmentation::new(text, score, words)).collect()
     }
 
-    let [mut i, mut last_offset, _] = queue[last_queue]; // last element of queue
-    while i > 0 {
-        let [index, offset, _] = queue[i];
-        // since offset is an offset of vertex and each vertex position designate a first char of word..
-        result.push_front(&text[offset..last_offset]);
-        last_offset = offset; // move offset to beginning of character
+    /// Return every possible segmentation of given text.
+    /// 
+    /// Text is split by white space first. Each chunk is segmented through the same word graph as
+    /// [n_best](struct.Tokenizer.html#method.n_best) and every combination of paths of each chunk is
+    /// a segmentation. Neither [Mode](enum.Mode.html) nor [Policy](enum.Policy.html) is used so the
+    /// segmentations come in no particular order but each one has the same score as in `n_best`.
+    /// An empty text has exactly one segmentation without any token.
+    /// 
+    /// Every segmentation is built into one arena-based tree before the first one is returned.
+    /// The number of segmentations may grow exponentially with the length of text. Use `n_best`
+    /// when only a few best ones are needed.
+    /// 
+    /// For example:
+    /// ```no_run
+    /// use tokenizer::th;
+    /// let tokenizer = th::Tokenizer::new("path/to/dictionary.txt")?;
+    /// for segmentation in tokenizer.segmentations("ตากลม") {
+    ///     println!("{:?}", segmentation.tokens.iter().map(|t| t.text).collect::<Vec<_>>());
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn segmentations<'a>(&self, text: &'a str) -> impl Iterator<Item=Segmentation<'a>> {
+        let log_total = (self.total_frequency.max(1) as f64).ln();
+        let mut tree = Tree::new();
+        // Leaves of every segmentation of chunks so far. Root node is the only leaf before the first chunk.
+        let mut leaves = vec![LeafNode {node: Tree::<(&str, bool)>::ROOT, score: PathScore::default()}];
 
-        i = index; // move index to another node in queue
+        for chunk in text.split_whitespace() {
+            let mut chunk_leaves = Vec::new();
+            make_result_tree(&self.dict, chunk, &mut tree, &leaves, &mut chunk_leaves, log_total);
+            leaves = chunk_leaves;
+        }
+
+        leaves.into_iter().map(move |leaf| {
+            let words = if leaf.node == Tree::<(&str, bool)>::ROOT {Vec::new()} else {tree.to_vec(leaf.node)};
+            Segmentation::new(text, leaf.score, words)
+        })
     }
+}
 
-    // first word
-    result.push_front(&text[0..last_offset]);
-    
-    result.into()
+impl<D: Lookup> Tokenizer<D> {
+    /// Return every candidate word that dictionary allow in given text.
+    /// 
+    /// Text is split by white space first. Each chunk is a separated [word_graph](fn.word_graph.html).
+    /// Every path from the beginning to the end of a chunk in the lattice is a possible segmentation
+    /// of that chunk. The path chosen by [tokenize](/tokenizer/trait.Tokenizer.html#tymethod.tokenize)
+    /// is one of them in [Mode::MaximalMatching](enum.Mode.html#variant.MaximalMatching).
+    /// 
+    /// For example, to inspect ambiguities with Graphviz:
+    /// ```no_run
+    /// use tokenizer::th;
+    /// let tokenizer = th::Tokenizer::new("path/to/dictionary.txt")?;
+    /// std::fs::write("lattice.dot", tokenizer.lattice("ตากลม").to_dot())?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn lattice<'a>(&self, text: &'a str) -> Lattice<'a> {
+        use crate::tokenizer::subslice_offset;
+
+        let mut vertices = Vec::new();
+        let mut edges = Vec::new();
+
+        for chunk in text.split_whitespace() {
+            let chunk_offset = subslice_offset(text, chunk);
+            let graph = word_graph(&self.dict, chunk);
+            let mut reachable = vec![false; chunk.len() + 1];
+            reachable[0] = true;
+
+            for (offset, words) in graph.iter().enumerate() {
+                if !reachable[offset] {
+                    continue
+                }
+
+                vertices.push(chunk_offset + offset);
+
+                for (end, frequency, known) in words.iter() {
+                    reachable[*end] = true;
+                    edges.push(lattice::Edge {
+                        text: &chunk[offset..*end],
+                        start: chunk_offset + offset,
+                        end: chunk_offset + end,
+                        known: *known,
+                        frequency: *frequency
+                    });
+                }
+            }
+        }
+
+        Lattice {
+            text,
+            vertices,
+            edges
+        }
+    }
 }
 
-/// Dictionary based Thai text tokenizer
-pub struct Tokenizer {
-    dict: crate::dict::SizedDict,
+/// Identify kind of each word.
+/// 
+/// A known word is `Known`. An unknown word is classified by [TokenKind::of](/tokenizer/enum.TokenKind.html#method.of).
+/// If it is `Unknown`, each run of digits or punctuation in it become its own `Number` or `Punctuation` token
+/// and each part between them is classified again, e.g. `๑๒คน,` become `๑๒`, `คน` and `,`.
+/// A known word is never split.
+fn words_kinds(words: Vec<(&str, bool)>) -> Vec<(&str, TokenKind)> {
+    let mut tokens = Vec::with_capacity(words.len());
+
+    for (word, known) in words {
+        let kind = if known {TokenKind::Known} else {TokenKind::of(word)};
+
+        if kind != TokenKind::Unknown {
+            tokens.push((word, kind));
+            continue
+        }
+
+        let mut start = 0;
+
+        for (run_start, run_end, kind) in split_kinds(word) {
+            if kind == TokenKind::Number || kind == TokenKind::Punctuation {
+                if start < run_start {
+                    tokens.push((&word[start..run_start], TokenKind::of(&word[start..run_start])));
+                }
+                tokens.push((&word[run_start..run_end], kind));
+                start = run_end;
+            }
+        }
+
+        if start < word.len() {
+            tokens.push((&word[start..], TokenKind::of(&word[start..])));
+        }
+    }
+
+    tokens
 }
 
 impl Tokenizer {
@@ -350,10 +856,83 @@ impl Tokenizer {
     /// กรุงเทพมหานคร
     /// จังหวัด
     /// ```
-    pub fn new<P: AsRef<std::path::Path>>(dict_path: P) -> std::io::Result<Tokenizer> {
-        Ok(Tokenizer {
-            dict: crate::dict::Dict::load_txt(dict_path)?.into()
-        })
+    /// 
+    /// Bad lines, e.g. an empty line or invalid UTF-8, are skipped. See [LoadPolicy](/tokenizer/dict/enum.LoadPolicy.html).
+    /// It return an error with the file name if the file cannot be read.
+    pub fn new<P: AsRef<std::path::Path>>(dict_path: P) -> crate::Result<Tokenizer> {
+        Ok(Tokenizer::with_dict(crate::dict::Dict::load_txt(dict_path)?.into()))
+    }
+}
+
+impl Tokenizer<SizedDict<u64>> {
+    /// Construct a Thai tokenizer using a precompiled binary dictionary.
+    /// 
+    /// It is much faster than [new](struct.Tokenizer.html#method.new) on large dictionary
+    /// as it doesn't need to parse text and rebuild dictionary.
+    /// The binary dictionary can be made by [save_compiled](struct.Tokenizer.html#method.save_compiled).
+    /// Frequency of each word is kept so [Mode::Unigram](enum.Mode.html#variant.Unigram) work the same
+    /// as on the dictionary that was saved.
+    /// 
+    /// It return an error with the file name if the file cannot be read. The error is of kind
+    /// `InvalidData` if the file is not a valid binary dictionary.
+    pub fn from_compiled<P: AsRef<std::path::Path>>(bin_path: P) -> crate::Result<Tokenizer<SizedDict<u64>>> {
+        Ok(Tokenizer::with_dict(SizedDict::load_with_frequency(bin_path)?))
+    }
+}
+
+impl<V: Frequency> Tokenizer<SizedDict<V>> {
+    /// Save dictionary of this tokenizer into binary format so it can be loaded later by
+    /// [from_compiled](struct.Tokenizer.html#method.from_compiled). Frequency of each word is saved.
+    /// 
+    /// For example:
+    /// ```no_run
+    /// use tokenizer::th;
+    /// th::Tokenizer::new("path/to/dictionary.txt")?.save_compiled("path/to/dictionary.bin")?;
+    /// // Later on
+    /// let tokenizer = th::Tokenizer::from_compiled("path/to/dictionary.bin")?;
+    /// # Ok::<(), tokenizer::Error>(())
+    /// ```
+    /// 
+    /// It return an error with the file name if the file cannot be written.
+    pub fn save_compiled<P: AsRef<std::path::Path>>(&self, bin_path: P) -> crate::Result<()> {
+        self.dict.save(bin_path)
+    }
+}
+
+#[cfg(feature="mmap")]
+impl Tokenizer<crate::dict::MmapDict> {
+    /// Construct a Thai tokenizer that memory-map a precompiled binary dictionary.
+    /// 
+    /// Unlike [from_compiled](struct.Tokenizer.html#method.from_compiled), the dictionary is
+    /// not deserialized. Every process that map the same file share it through page cache.
+    /// The binary dictionary can be made by [save_compiled](struct.Tokenizer.html#method.save_compiled).
+    /// See [MmapDict](/tokenizer/dict/struct.MmapDict.html) for precaution on modifying the file.
+    /// 
+    /// It return an error with the file name if the file cannot be mapped. The error is of kind
+    /// `InvalidData` if the file is not a valid binary dictionary.
+    pub fn from_mmap<P: AsRef<std::path::Path>>(bin_path: P) -> crate::Result<Tokenizer<crate::dict::MmapDict>> {
+        Ok(Tokenizer::with_dict(crate::dict::MmapDict::open(bin_path)?))
+    }
+}
+
+#[cfg(feature="bundled-dict")]
+impl Tokenizer<crate::dict::DictView<'static>> {
+    /// Construct a Thai tokenizer using a small dictionary of common Thai words that is
+    /// embedded into the binary. It need no dictionary file.
+    /// 
+    /// The dictionary is compiled at build time so this is cheap. It only cover common words
+    /// so use [new](struct.Tokenizer.html#method.new) with a larger dictionary for better accuracy.
+    /// See [dict::bundled](/tokenizer/dict/fn.bundled.html).
+    pub fn bundled() -> Tokenizer<crate::dict::DictView<'static>> {
+        Tokenizer::with_dict(crate::dict::bundled())
+    }
+}
+
+/// Same as [Tokenizer::bundled](struct.Tokenizer.html#method.bundled).
+#[cfg(feature="bundled-dict")]
+impl Default for Tokenizer<crate::dict::DictView<'static>> {
+    fn default() -> Tokenizer<crate::dict::DictView<'static>> {
+        Tokenizer::bundled()
     }
 }
 
@@ -362,9 +941,7 @@ impl From<&[&str]> for Tokenizer {
     fn from(slice: &[&str]) -> Tokenizer {
         let mut dict = crate::dict::Dict::new();
         slice.iter().for_each(|word| {dict.add(word)});
-        Tokenizer {
-            dict: dict.into()
-        }
+        Tokenizer::with_dict(dict.into())
     }
 }
 
@@ -373,9 +950,7 @@ impl From<&[&String]> for Tokenizer {
     fn from(slice: &[&String]) -> Tokenizer {
         let mut dict = crate::dict::Dict::new();
         slice.iter().for_each(|word| {dict.add(word)});
-        Tokenizer {
-            dict: dict.into()
-        }
+        Tokenizer::with_dict(dict.into())
     }
 }
 
@@ -384,60 +959,164 @@ impl From<&[String]> for Tokenizer {
     fn from(slice: &[String]) -> Tokenizer {
         let mut dict = crate::dict::Dict::new();
         slice.iter().for_each(|word| {dict.add(word)});
-        Tokenizer {
-            dict: dict.into()
-        }
+        Tokenizer::with_dict(dict.into())
     }
 }
 
-impl crate::tokenizer::Tokenizer for Tokenizer {
-    fn tokenize<'b>(&self, value: &'b str) -> Vec<&'b str> {
-        #[cfg(not(feature="single-thread"))]
-        use rayon::iter::ParallelIterator;
-        
-        #[cfg(not(feature="single-thread"))]
-        fn make_iter(raw: &str) -> rayon::str::SplitWhitespace {
-            use rayon::prelude::*;
-
-            raw.par_split_whitespace()
+/// Split text into runs of white space and runs of everything else.
+/// Each run come with a flag telling whether it is white space.
+fn whitespace_runs(text: &str) -> impl Iterator<Item=(&str, bool)> + '_ {
+    let mut rest = text;
+
+    std::iter::from_fn(move || {
+        let is_whitespace = rest.chars().next()?.is_whitespace();
+        let len = rest.find(|c: char| c.is_whitespace() != is_whitespace).unwrap_or(rest.len());
+        let (run, remain) = rest.split_at(len);
+        rest = remain;
+        Some((run, is_whitespace))
+    })
+}
+
+impl<D: Lookup> Tokenizer<D> {
+    /// Tokenize given text and identify kind of each token.
+    /// 
+    /// A word found by algorithm of current [Mode](enum.Mode.html) is `Known`.
+    /// An unknown word is classified and split by [words_kinds](fn.words_kinds.html).
+    fn segment<'a>(&self, text: &'a str) -> Vec<(&'a str, TokenKind)> {
+        words_kinds(match self.mode {
+            Mode::MaximalMatching => maximal_matching(&self.dict, text, self.policy),
+            Mode::Unigram => unigram(&self.dict, text, self.total_frequency),
+        })
+    }
+
+    /// Tokenize a run of text given by [whitespace_runs](fn.whitespace_runs.html).
+    /// 
+    /// A run of white space is a single token. If punctuation is a separator, the run is split
+    /// at every run of punctuation before it is tokenized.
+    fn segment_run<'a>(&self, run: &'a str, is_whitespace: bool) -> Vec<(&'a str, TokenKind)> {
+        if is_whitespace {
+            return vec![(run, TokenKind::Whitespace)]
+        } else if self.separators != Separators::WhitespaceAndPunctuation {
+            return self.segment(run)
         }
-        #[cfg(feature="single-thread")]
-        fn make_iter(raw: &str) -> std::str::SplitWhitespace {
-            raw.split_whitespace()
+
+        let mut tokens = Vec::new();
+        let mut start = 0;
+
+        for (punct_start, punct_end, kind) in split_kinds(run) {
+            if kind == TokenKind::Punctuation {
+                if start < punct_start {
+                    tokens.extend(self.segment(&run[start..punct_start]));
+                }
+                tokens.push((&run[punct_start..punct_end], kind));
+                start = punct_end;
+            }
         }
 
-        make_iter(value).map(|boundary| {
-            // let mut leaf_nodes = Vec::new();
-            // let root = TreeNode::root();
-            // make_result_tree(&self.dict.root, boundary, root, &mut leaf_nodes);
-            // let mut min_count = boundary.len(); // worst case length
-            // let mut min_unknown = boundary.len(); // Impossible case where each char is treat as unknown token
-            // let mut idx = 0;
-            // // Maximum matching approach, find a path with least node
-            // leaf_nodes.iter().enumerate().for_each(|(i, n)| {
-            //     let level = n.node.level();
-            //     let unknown = n.unknown_bytes_count;
-
-            //     if unknown < min_unknown {
-            //         min_unknown = unknown;
-
-            //         if level <= min_count {
-            //             // New best case, lower unknown and max matched
-            //             min_count = level;
-            //             idx = i;
-            //         }
-            //     } else {
-            //         if level < min_count {
-            //             // Subjective case, more unknown tokens but better matched
-            //             // Need more sophisticate approach to solve this
-            //         }
-            //     }
-            // });
-            // let expected_node = leaf_nodes.remove(idx);
-            // let result = expected_node.node.into_vec();
-            // result
-            maximal_matching(&self.dict.root, boundary)
-        }).flatten().collect()
+        if start < run.len() {
+            tokens.extend(self.segment(&run[start..]));
+        }
+
+        tokens
+    }
+
+    /// Tokenize given text then call `f` with each token and its kind and append the result into `out`.
+    /// 
+    /// Chunks of text are tokenized according to [parallelism](struct.Tokenizer.html#method.parallelism).
+    /// White space is kept according to [separators](struct.Tokenizer.html#method.separators).
+    fn extend_tokens<'a, T, F>(&self, text: &'a str, out: &mut Vec<T>, f: F) where T: Send, F: Fn(&'a str, TokenKind) -> T + Send + Sync {
+        let f = &f;
+
+        if self.separators == Separators::Discard {
+            self.parallelism.extend_chunks(text, out, |chunk| {
+                self.segment(chunk).into_iter().map(move |(token, kind)| f(token, kind))
+            });
+        } else {
+            let runs: Vec<(&str, bool)> = whitespace_runs(text).collect();
+            let tokens = self.parallelism.map_batch(&runs, 1, |&(run, is_whitespace)| self.segment_run(run, is_whitespace));
+            out.extend(tokens.into_iter().flatten().map(|(token, kind)| f(token, kind)));
+        }
+    }
+}
+
+impl<D: Lookup> Tokenizer<D> {
+    /// Tokenize many documents and return tokens of each document in the same order as `documents`.
+    /// 
+    /// Unlike [tokenize](/tokenizer/trait.Tokenizer.html#tymethod.tokenize) which spread white space
+    /// separated chunks of a single text across threads, it spread documents across threads according to
+    /// [parallelism](struct.Tokenizer.html#method.parallelism) and tokenize each document on a single thread.
+    /// It is much faster for a lot of short documents.
+    /// Each thread take at least [batch_min_len](struct.Tokenizer.html#method.batch_min_len) documents at a time.
+    /// ```
+    /// use tokenizer::th;
+    /// let tokenizer = th::Tokenizer::from(&["ภาษา", "ไทย", "ง่าย"][..]).with_batch_min_len(64);
+    /// let titles = vec!["ภาษาไทย", "ไทย ง่าย"];
+    /// assert_eq!(tokenizer.tokenize_batch(&titles), vec![vec!["ภาษา", "ไทย"], vec!["ไทย", "ง่าย"]]);
+    /// ```
+    pub fn tokenize_batch<'b, S>(&self, documents: &'b [S]) -> Vec<Vec<&'b str>> where S: AsRef<str> + Sync {
+        use crate::tokenizer::Tokenizer;
+
+        self.parallelism.map_batch(documents, self.batch_min_len, |document| {
+            self.tokenize_iter(document.as_ref()).collect()
+        })
+    }
+
+    /// Tokenize documents from a Rayon parallel iterator and return tokens of each document in
+    /// the same order as the iterator.
+    /// 
+    /// It is for documents that doesn't come as a slice, e.g. lines of a large text read by
+    /// `par_lines`. Each document is tokenized on a single thread. If [parallelism](struct.Tokenizer.html#method.parallelism)
+    /// is [Parallelism::Pool](/tokenizer/enum.Parallelism.html#variant.Pool), it run on that pool.
+    /// Otherwise, it run on current Rayon thread pool as parallel iterator usually does.
+    #[cfg(feature="multi-thread")]
+    pub fn par_tokenize_batch<'b, I>(&self, documents: I) -> Vec<Vec<&'b str>> where I: rayon::iter::IntoParallelIterator<Item=&'b str> {
+        use rayon::prelude::*;
+        use crate::tokenizer::Tokenizer;
+
+        let documents = documents.into_par_iter();
+        let tokenize = || documents.map(|document| self.tokenize_iter(document).collect()).collect();
+
+        match &self.parallelism {
+            Parallelism::Pool(pool) => pool.install(tokenize),
+            _ => tokenize()
+        }
+    }
+}
+
+impl<D: Lookup> crate::tokenizer::Tokenizer for Tokenizer<D> {
+    fn tokenize<'b>(&self, value: &'b str) -> Vec<&'b str> {
+        let mut tokens = Vec::new();
+        self.tokenize_into(value, &mut tokens);
+        tokens
+    }
+
+    /// Tokenize given text lazily. Each white space separated chunk is tokenized only when
+    /// the iterator reach it. It is always done on current thread.
+    fn tokenize_iter<'b>(&self, value: &'b str) -> impl Iterator<Item=&'b str> {
+        let keep_whitespace = self.separators != Separators::Discard;
+
+        whitespace_runs(value).filter(move |&(_, is_whitespace)| keep_whitespace || !is_whitespace).flat_map(move |(run, is_whitespace)| {
+            self.segment_run(run, is_whitespace).into_iter().map(|(token, _)| token)
+        })
+    }
+
+    fn tokenize_into<'b>(&self, value: &'b str, tokens: &mut Vec<&'b str>) {
+        self.extend_tokens(value, tokens, |token, _| token);
+    }
+}
+
+impl<D: Lookup> crate::tokenizer::SpanTokenizer for Tokenizer<D> {
+    fn tokenize_spans<'b>(&self, value: &'b str) -> Vec<crate::tokenizer::Token<'b>> {
+        use crate::tokenizer::make_tokens;
+
+        // Every token is a slice of `value` so its offset can be found directly.
+        let mut ranges: Vec<(usize, usize, TokenKind)> = Vec::new();
+        self.extend_tokens(value, &mut ranges, |token, kind| {
+            let start = subslice_offset(value, token);
+            (start, start + token.len(), kind)
+        });
+
+        make_tokens(value, ranges)
     }
 }
 
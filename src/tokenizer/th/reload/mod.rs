@@ -0,0 +1,220 @@
+//! Swap dictionary of a tokenizer while other threads are using it.
+//! 
+//! [Tokenizer](../struct.Tokenizer.html) own its dictionary immutably. A long running service
+//! would need a restart to use an updated dictionary. [ReloadableTokenizer](struct.ReloadableTokenizer.html)
+//! keep current tokenizer behind an `Arc`. Each tokenize call take the current one once at the
+//! beginning and use it until the end. Swapping a dictionary only replace the `Arc` so calls that
+//! are in progress finish on the old dictionary and every later call see the new one. The old
+//! dictionary is dropped when the last call that use it finish.
+//! 
+//! [DictWatcher](struct.DictWatcher.html) poll a dictionary file and reload it when it is modified.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+use crate::dict::{self, FromFrequency, Lookup, SizedDict};
+use super::Tokenizer;
+
+/// A Thai tokenizer whose dictionary can be swapped while other threads are tokenizing.
+/// 
+/// It implement [Tokenizer](/tokenizer/trait.Tokenizer.html) trait so it can be used in place of
+/// [th::Tokenizer](../struct.Tokenizer.html). Every configuration, e.g. mode and separators,
+/// is kept when dictionary is swapped.
+/// ```no_run
+/// use std::sync::Arc;
+/// use tokenizer::{Tokenizer, dict::Dict, th};
+/// let tokenizer = Arc::new(th::ReloadableTokenizer::new(th::Tokenizer::new("path/to/dictionary.txt")?));
+/// // On other threads
+/// let tokens = tokenizer.tokenize("ภาษาไทยง่ายนิดเดียว");
+/// // Later on, when dictionary is updated
+/// tokenizer.reload_txt("path/to/dictionary.txt")?;
+/// // Or from a dictionary built in memory
+/// let mut dict = Dict::new();
+/// dict.add("ภาษาไทย");
+/// tokenizer.swap(dict);
+/// # Ok::<(), tokenizer::Error>(())
+/// ```
+pub struct ReloadableTokenizer<D = SizedDict> {
+    current: RwLock<Arc<Tokenizer<D>>>,
+    /// Held by [swap](struct.ReloadableTokenizer.html#method.swap) and [replace](struct.ReloadableTokenizer.html#method.replace)
+    /// so a tokenizer built from current configuration is never put over a newer one.
+    writer: Mutex<()>,
+}
+
+impl<D: Lookup> ReloadableTokenizer<D> {
+    /// Construct a reloadable tokenizer that start with given tokenizer.
+    pub fn new(tokenizer: Tokenizer<D>) -> ReloadableTokenizer<D> {
+        ReloadableTokenizer {
+            current: RwLock::new(Arc::new(tokenizer)),
+            writer: Mutex::new(())
+        }
+    }
+
+    /// Get current tokenizer. It keep using the same dictionary even if the dictionary is swapped
+    /// afterward so many calls can be made on the same version of dictionary.
+    pub fn current(&self) -> Arc<Tokenizer<D>> {
+        // A panic while holding the lock can't leave the `Arc` in a bad state.
+        Arc::clone(&self.current.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Replace dictionary of this tokenizer. The configuration of current tokenizer is kept.
+    /// 
+    /// Calls that is in progress finish on the old dictionary.
+    /// It return the tokenizer that was in use.
+    /// 
+    /// Concurrent swaps and [replace](struct.ReloadableTokenizer.html#method.replace) are applied one
+    /// at a time so a configuration set by `replace` is never lost.
+    pub fn swap<T: Into<D>>(&self, dict: T) -> Arc<Tokenizer<D>> {
+        let dict = dict.into();
+        let _writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        // Build the new tokenizer before taking the write lock so readers never wait for it.
+        let tokenizer = Arc::new(self.current().with_same_config(dict));
+        self.store(tokenizer)
+    }
+
+    /// Replace the whole tokenizer, including its configuration.
+    /// It return the tokenizer that was in use.
+    pub fn replace(&self, tokenizer: Arc<Tokenizer<D>>) -> Arc<Tokenizer<D>> {
+        let _writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        self.store(tokenizer)
+    }
+
+    /// Put given tokenizer in use. Caller must hold the writer lock.
+    fn store(&self, tokenizer: Arc<Tokenizer<D>>) -> Arc<Tokenizer<D>> {
+        std::mem::replace(&mut *self.current.write().unwrap_or_else(|e| e.into_inner()), tokenizer)
+    }
+}
+
+impl<V: FromFrequency + Send + Sync + 'static> ReloadableTokenizer<SizedDict<V>> {
+    /// Load a text dictionary file and swap it in. See [Dict::load_txt](/tokenizer/dict/struct.Dict.html#method.load_txt).
+    /// Each word has frequency 1 as text file has no frequency.
+    /// 
+    /// If the file can't be loaded, current dictionary is kept.
+    pub fn reload_txt<P: AsRef<Path>>(&self, dict_path: P) -> crate::Result<()> {
+        self.swap(dict::load_txt::<V>(dict_path.as_ref())?);
+        Ok(())
+    }
+
+    /// Load a binary dictionary file and swap it in. See [SizedDict::load_with_frequency](/tokenizer/dict/struct.SizedDict.html#method.load_with_frequency).
+    /// The payload of each word is made from its frequency, e.g. it is dropped if this dictionary has no payload.
+    /// 
+    /// If the file can't be loaded, current dictionary is kept.
+    pub fn reload_compiled<P: AsRef<Path>>(&self, bin_path: P) -> crate::Result<()> {
+        self.swap(dict::load_compiled::<V>(bin_path.as_ref())?);
+        Ok(())
+    }
+
+    /// Reload text dictionary file by [reload_txt](struct.ReloadableTokenizer.html#method.reload_txt)
+    /// whenever it is modified. See [DictWatcher](struct.DictWatcher.html).
+    pub fn watch_txt<P: AsRef<Path>>(self: &Arc<Self>, dict_path: P, interval: Duration) -> DictWatcher {
+        self.watch(dict_path, interval, dict::load_txt::<V>)
+    }
+}
+
+impl<D: Lookup + 'static> ReloadableTokenizer<D> {
+    /// Poll given file on a background thread every `interval`. When its modified time change,
+    /// `load` is called with the path and the loaded dictionary is swapped in.
+    /// 
+    /// If `load` fail, current dictionary is kept and the error can be taken from returned
+    /// [DictWatcher](struct.DictWatcher.html). The file is tried again on next change.
+    /// The watching stop when the watcher is dropped.
+    pub fn watch<P, F>(self: &Arc<Self>, dict_path: P, interval: Duration, mut load: F) -> DictWatcher
+    where P: AsRef<Path>, F: FnMut(&Path) -> crate::Result<D> + Send + 'static {
+        let path = dict_path.as_ref().to_owned();
+        let tokenizer = Arc::downgrade(self);
+        let error = Arc::new(Mutex::new(None));
+        let last_error = Arc::clone(&error);
+        let (stop, stopped) = std::sync::mpsc::channel::<()>();
+        let mut modified = modified_time(&path);
+
+        let handle = std::thread::spawn(move || {
+            // Wake up every interval until the watcher is dropped which disconnect the channel.
+            while let Err(std::sync::mpsc::RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let Some(tokenizer) = tokenizer.upgrade() else {break};
+                let time = modified_time(&path);
+
+                // The file may be missing while it is being replaced. Wait until it is back.
+                if time.is_none() || time == modified {
+                    continue
+                }
+
+                modified = time;
+                match load(&path) {
+                    Ok(dict) => {
+                        tokenizer.swap(dict);
+                    },
+                    Err(e) => *last_error.lock().unwrap_or_else(|e| e.into_inner()) = Some(e)
+                }
+            }
+        });
+
+        DictWatcher {
+            path: dict_path.as_ref().to_owned(),
+            stop: Some(stop),
+            handle: Some(handle),
+            error
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl<D: Lookup> crate::tokenizer::Tokenizer for ReloadableTokenizer<D> {
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.current().tokenize(text)
+    }
+
+    fn tokenize_into<'a>(&self, text: &'a str, tokens: &mut Vec<&'a str>) {
+        self.current().tokenize_into(text, tokens)
+    }
+}
+
+impl<D: Lookup> crate::tokenizer::SpanTokenizer for ReloadableTokenizer<D> {
+    fn tokenize_spans<'a>(&self, text: &'a str) -> Vec<crate::tokenizer::Token<'a>> {
+        self.current().tokenize_spans(text)
+    }
+}
+
+/// A background thread that reload a dictionary file when it is modified.
+/// 
+/// It is made by [watch](struct.ReloadableTokenizer.html#method.watch) or
+/// [watch_txt](struct.ReloadableTokenizer.html#method.watch_txt). Modification is detected by
+/// polling modified time of the file so it work on every platform without any dependency.
+/// To avoid loading a partially written file, write new dictionary to other file then rename it
+/// over the watched file.
+/// 
+/// The thread stop when this watcher is dropped or when the tokenizer is dropped.
+pub struct DictWatcher {
+    path: PathBuf,
+    stop: Option<std::sync::mpsc::Sender<()>>,
+    handle: Option<std::thread::JoinHandle<()>>,
+    error: Arc<Mutex<Option<crate::Error>>>,
+}
+
+impl DictWatcher {
+    /// The watched file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Take the error of the last failed reload, if any.
+    pub fn take_error(&self) -> Option<crate::Error> {
+        self.error.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
+impl Drop for DictWatcher {
+    fn drop(&mut self) {
+        // Disconnect the channel to wake the thread up then wait for it to finish.
+        self.stop.take();
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,126 @@
+use super::*;
+use crate::dict::tests::{dict, frequency_dict};
+use crate::tokenizer::Tokenizer as _;
+
+#[test]
+fn test_swap() {
+    let tokenizer = ReloadableTokenizer::new(Tokenizer::from(&["การ", "บ้าน"][..]).with_separators(super::super::Separators::Whitespace));
+    assert_eq!(tokenizer.tokenize("การบ้าน ดี"), vec!["การ", "บ้าน", " ", "ดี"]);
+
+    // A snapshot keep using the old dictionary
+    let old = tokenizer.current();
+    tokenizer.swap(dict(&["การบ้าน", "ดี"]));
+    assert_eq!(old.tokenize("การบ้าน"), vec!["การ", "บ้าน"]);
+    // Configuration is kept
+    assert_eq!(tokenizer.tokenize("การบ้าน ดี"), vec!["การบ้าน", " ", "ดี"]);
+    assert_eq!(tokenizer.current().separators(), super::super::Separators::Whitespace);
+}
+
+#[test]
+fn test_swap_while_tokenizing() {
+    let tokenizer = Arc::new(ReloadableTokenizer::new(Tokenizer::from(&["การ", "บ้าน"][..])));
+    let threads: Vec<_> = (0..4).map(|_| {
+        let tokenizer = Arc::clone(&tokenizer);
+        std::thread::spawn(move || {
+            for _ in 0..200 {
+                let tokens = tokenizer.tokenize("การบ้านการบ้าน");
+                // Every call see exactly one version of dictionary
+                assert!(tokens == vec!["การ", "บ้าน", "การ", "บ้าน"] || tokens == vec!["การบ้าน", "การบ้าน"], "{:?}", tokens);
+            }
+        })
+    }).collect();
+
+    for i in 0..200 {
+        let words: &[&str] = if i % 2 == 0 {&["การบ้าน"]} else {&["การ", "บ้าน"]};
+        tokenizer.swap(dict(words));
+    }
+
+    threads.into_iter().for_each(|t| t.join().unwrap());
+}
+
+#[test]
+fn test_replace_while_swapping() {
+    use super::super::Separators;
+
+    let tokenizer = Arc::new(ReloadableTokenizer::new(Tokenizer::from(&["การ", "บ้าน"][..])));
+    let threads: Vec<_> = (0..4).map(|_| {
+        let tokenizer = Arc::clone(&tokenizer);
+        std::thread::spawn(move || (0..200).for_each(|_| {tokenizer.swap(dict(&["การบ้าน"]));}))
+    }).collect();
+
+    // A swap that begin before this replace shall not put the old configuration back
+    tokenizer.replace(Arc::new(Tokenizer::from(&["การ"][..]).with_separators(Separators::Whitespace)));
+    threads.into_iter().for_each(|t| t.join().unwrap());
+    assert_eq!(tokenizer.current().separators(), Separators::Whitespace);
+}
+
+#[test]
+fn test_reload_file() {
+    let path = std::env::temp_dir().join(format!("tokenizer_reload_{}.txt", std::process::id()));
+    std::fs::write(&path, "การ\nบ้าน\n").unwrap();
+    let tokenizer = ReloadableTokenizer::new(Tokenizer::new(&path).unwrap());
+    assert_eq!(tokenizer.tokenize("การบ้าน"), vec!["การ", "บ้าน"]);
+
+    std::fs::write(&path, "การบ้าน\n").unwrap();
+    tokenizer.reload_txt(&path).unwrap();
+    assert_eq!(tokenizer.tokenize("การบ้าน"), vec!["การบ้าน"]);
+
+    // A failed reload keep current dictionary
+    std::fs::remove_file(&path).unwrap();
+    assert!(tokenizer.reload_txt(&path).is_err());
+    assert_eq!(tokenizer.tokenize("การบ้าน"), vec!["การบ้าน"]);
+
+    let bin_path = path.with_extension("bin");
+    dict(&["การ", "บ้าน"]).save(&bin_path).unwrap();
+    tokenizer.reload_compiled(&bin_path).unwrap();
+    assert_eq!(tokenizer.tokenize("การบ้าน"), vec!["การ", "บ้าน"]);
+    std::fs::remove_file(&bin_path).unwrap();
+    let err = tokenizer.reload_compiled(&bin_path).unwrap_err();
+    assert!(matches!(&err, crate::Error::Io {path: Some(p), ..} if p == &bin_path));
+    assert_eq!(tokenizer.tokenize("การบ้าน"), vec!["การ", "บ้าน"]);
+}
+
+#[test]
+fn test_reload_file_with_frequency() {
+    let path = std::env::temp_dir().join(format!("tokenizer_reload_frequency_{}.txt", std::process::id()));
+    let bin_path = path.with_extension("bin");
+    frequency_dict(&[("การ", 7), ("บ้าน", 3)]).save(&bin_path).unwrap();
+
+    let tokenizer = ReloadableTokenizer::new(Tokenizer::from_compiled(&bin_path).unwrap());
+    std::fs::write(&path, "การบ้าน\n").unwrap();
+    tokenizer.reload_txt(&path).unwrap();
+    assert_eq!(tokenizer.tokenize("การบ้าน"), vec!["การบ้าน"]);
+    assert_eq!(tokenizer.current().dict().total_frequency(), 1);
+
+    // Frequency is kept when reloading binary dictionary
+    tokenizer.reload_compiled(&bin_path).unwrap();
+    assert_eq!(tokenizer.tokenize("การบ้าน"), vec!["การ", "บ้าน"]);
+    assert_eq!(tokenizer.current().dict().total_frequency(), 10);
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&bin_path).unwrap();
+}
+
+#[test]
+fn test_watch() {
+    let path = std::env::temp_dir().join(format!("tokenizer_watch_{}.txt", std::process::id()));
+    std::fs::write(&path, "การ\nบ้าน\n").unwrap();
+    let tokenizer = Arc::new(ReloadableTokenizer::new(Tokenizer::new(&path).unwrap()));
+    let watcher = tokenizer.watch_txt(&path, Duration::from_millis(10));
+    assert_eq!(watcher.path(), path);
+
+    // Make sure modified time change even on file system with coarse timestamp
+    let file = std::fs::File::options().write(true).truncate(true).open(&path).unwrap();
+    std::io::Write::write_all(&mut &file, "การบ้าน\n".as_bytes()).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(10)).unwrap();
+    drop(file);
+
+    let reloaded = (0..500).any(|_| {
+        std::thread::sleep(Duration::from_millis(10));
+        tokenizer.tokenize("การบ้าน") == vec!["การบ้าน"]
+    });
+    assert!(reloaded);
+    assert!(watcher.take_error().is_none());
+
+    drop(watcher);
+    std::fs::remove_file(&path).unwrap();
+}
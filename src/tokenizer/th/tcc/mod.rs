@@ -0,0 +1,400 @@
+//! Thai Character Cluster (TCC) rules.
+//!
+//! A Thai character cluster is an inseparable group of characters. For example, a consonant and
+//! its following vowel or tone mark, or a leading vowel and its consonant. A word boundary can
+//! only be at an edge of a cluster. Cutting text anywhere else produce invalid Thai token.
+//!
+//! Each rule is a sequence of items. The rules are written below in a compact notation:
+//! - `c` - A Thai consonant, `ก` to `ฮ`.
+//! - `t` - An optional tone mark, `่` to `๋`.
+//! - `k` - An optional silent consonants with thanthakhat, e.g. `ร์` in `จันทร์`. It is `cc?[ิุ]?์`.
+//! - `[...]` - One of the characters inside brackets.
+//! - `?` - The preceding item is optional.
+//! - Any other character match itself.
+//!
+//! A cluster at any position is the longest match of every rules. If no rule match, the cluster
+//! is a single character. A cluster that has a Thai consonant also take every following character
+//! that can never begin a cluster, such as an above vowel, a below vowel, a tone mark or sara am.
+//!
+//! Clusters can be grouped further into syllables by [syllables](fn.syllables.html).
+//! [ClusterTokenizer](struct.ClusterTokenizer.html) and [SyllableTokenizer](struct.SyllableTokenizer.html)
+//! split text into those units without any dictionary.
+
+use crate::tokenizer::{make_tokens, split_kinds, subslice_offset, Token, TokenKind};
+
+/// An item of a rule.
+#[derive(Clone, Copy)]
+enum Item {
+    /// A Thai consonant
+    Consonant,
+    /// One of given chars
+    Set(&'static [char]),
+    /// Silent consonants with thanthakhat, `cc?[ิุ]?์`
+    Silent,
+}
+
+/// An item and a flag telling whether it is optional.
+type Step = (Item, bool);
+
+const C: Step = (Item::Consonant, false);
+/// An optional tone mark
+const T: Step = (Item::Set(TONES), true);
+/// Optional silent consonants
+const K: Step = (Item::Silent, true);
+
+/// Exactly one of given chars.
+const fn one(set: &'static [char]) -> Step {
+    (Item::Set(set), false)
+}
+
+/// Optionally one of given chars.
+const fn opt(set: &'static [char]) -> Step {
+    (Item::Set(set), true)
+}
+
+/// Tone marks, mai ek, mai tho, mai tri and mai chattawa.
+const TONES: &[char] = &['่', '้', '๊', '๋'];
+
+/// Rules that begin with a consonant. It is based on rules used by "newmm" tokenizer.
+/// The first consonant is already matched so it is left out of each rule.
+const CONSONANT_RULES: &[&[Step]] = &[
+    &[one(&['ั']), T, one(&['ว']), one(&['ะ']), K],                              // cัtวะk
+    &[one(&['ั', 'ื']), T, C, opt(&['ุ', 'ิ', 'ะ']), K],                            // c[ัื]tc[ุิะ]?k
+    &[one(&['ิ', 'ุ', 'ู']), one(&['์'])],                                         // c[ิุู]์
+    &[one(&['ะ', 'ั', 'า', 'ำ', 'ิ', 'ี', 'ึ', 'ื', 'ุ', 'ู']), T, K],               // c[ะัาำิีึืุู]tk
+    &[one(&['็'])],                                                             // c็
+    &[K],                                                                       // ck
+];
+
+/// Rules that begin with sara e. The leading vowel is already matched so it is left out of each rule.
+const SARA_E_RULES: &[&[Step]] = &[
+    &[C, one(&['็']), C, K],                                                    // เc็ck
+    &[C, C, T, one(&['า']), one(&['ะ']), K],                                    // เcctาะk
+    &[C, C, one(&['ี']), T, one(&['ย']), one(&['ะ']), K],                        // เccีtยะk
+    &[C, C, one(&['ี']), T, one(&['ย']), K],                                    // เccีtยk
+    &[C, C, one(&['็']), C, K],                                                 // เcc็ck
+    &[C, one(&['ิ']), C, one(&['์']), C, K],                                     // เcิc์ck
+    &[C, one(&['ิ']), T, C, K],                                                 // เcิtck
+    &[C, one(&['ี']), T, one(&['ย']), opt(&['ะ']), K],                           // เcีtยะ?k
+    &[C, one(&['ื']), T, one(&['อ']), opt(&['ะ']), K],                           // เcืtอะ?k
+    &[C, T, opt(&['า']), opt(&['ะ']), K],                                       // เctา?ะ?k
+    &[C, C, T, opt(&['า']), opt(&['ะ']), K],                                    // เcctา?ะ?k
+    &[C, T],                                                                    // [เแโใไ]ct
+];
+
+/// Rules that begin with sara ae. The leading vowel is already matched so it is left out of each rule.
+const SARA_AE_RULES: &[&[Step]] = &[
+    &[C, one(&['็']), C],                                                       // แc็c
+    &[C, C, one(&['์'])],                                                       // แcc์
+    &[C, T, one(&['ะ'])],                                                       // แctะ
+    &[C, C, one(&['็']), C],                                                    // แcc็c
+    &[C, C, C, one(&['์'])],                                                    // แccc์
+    &[C, T],                                                                    // [เแโใไ]ct
+];
+
+/// Rules that begin with sara o. The leading vowel is already matched so it is left out of each rule.
+const SARA_O_RULES: &[&[Step]] = &[
+    &[C, T, one(&['ะ'])],                                                       // โctะ
+    &[C, T],                                                                    // [เแโใไ]ct
+];
+
+/// Rules that begin with sara ai maimuan or sara ai maimalai.
+/// The leading vowel is already matched so it is left out of each rule.
+const SARA_AI_RULES: &[&[Step]] = &[
+    &[C, T],                                                                    // [เแโใไ]ct
+];
+
+/// Return true if given char is a Thai consonant.
+#[inline(always)]
+fn is_consonant(c: char) -> bool {
+    ('ก'..='ฮ').contains(&c)
+}
+
+/// Return true if given char can never begin a cluster. It must be part of preceding cluster.
+///
+/// They are following vowels `ะ`, `า`, `ำ`, `ๅ`, every above and below vowel, tone marks and other diacritics.
+#[inline(always)]
+fn is_dependent(c: char) -> bool {
+    matches!(c, 'ะ'..='ฺ' | 'ๅ' | '็'..='๎')
+}
+
+/// Return a length in bytes of silent consonants with thanthakhat, `cc?[ิุ]?์`, at the beginning of `text`.
+#[inline]
+fn silent_len(text: &str) -> Option<usize> {
+    let mut chars = text.chars();
+    let mut len = 0;
+    let mut c = chars.next()?;
+
+    if !is_consonant(c) {
+        return None
+    }
+
+    // Every char of this pattern is Thai which is 3 bytes in UTF-8
+    len += 3;
+    c = chars.next()?;
+
+    if is_consonant(c) {
+        len += 3;
+        c = chars.next()?;
+    }
+
+    if c == 'ิ' || c == 'ุ' {
+        len += 3;
+        c = chars.next()?;
+    }
+
+    if c == '์' {
+        Some(len + 3)
+    } else {
+        None
+    }
+}
+
+impl Item {
+    /// Return a length in bytes of this item at the beginning of `text`.
+    #[inline]
+    fn match_len(self, text: &str) -> Option<usize> {
+        match self {
+            Item::Silent => silent_len(text),
+            Item::Consonant => text.chars().next().filter(|c| is_consonant(*c)).map(char::len_utf8),
+            Item::Set(set) => text.chars().next().filter(|c| set.contains(c)).map(char::len_utf8),
+        }
+    }
+}
+
+/// Return a length in bytes of longest prefix of `text` that match every step of a rule.
+/// It return None if there's no such prefix.
+fn longest_match(steps: &[Step], text: &str) -> Option<usize> {
+    let Some(((item, optional), rest)) = steps.split_first() else {return Some(0)};
+    let with_item = item.match_len(text).and_then(|len| longest_match(rest, &text[len..]).map(|remain| len + remain));
+    let without_item = if *optional {longest_match(rest, text)} else {None};
+
+    with_item.max(without_item)
+}
+
+/// Return a length in bytes of a cluster that begin at `offset` of `text`.
+///
+/// It return 0 if `offset` is at the end of text.
+pub fn cluster_len(text: &str, offset: usize) -> usize {
+    let text = &text[offset..];
+    let first = match text.chars().next() {
+        Some(c) => c,
+        None => return 0
+    };
+    let rules = match first {
+        'ก'..='ฮ' => CONSONANT_RULES,
+        'เ' => SARA_E_RULES,
+        'แ' => SARA_AE_RULES,
+        'โ' => SARA_O_RULES,
+        'ใ' | 'ไ' => SARA_AI_RULES,
+        // Any other char is a cluster of its own
+        _ => return first.len_utf8()
+    };
+    let rest = &text[first.len_utf8()..];
+    let matched = rules.iter().filter_map(|rule| longest_match(rule, rest)).max().map(|len| first.len_utf8() + len);
+
+    // A leading vowel without consonant is a cluster of its own
+    let Some(mut len) = matched.or_else(|| is_consonant(first).then(|| first.len_utf8())) else {
+        return first.len_utf8()
+    };
+
+    for c in text[len..].chars() {
+        if !is_dependent(c) {
+            break
+        }
+        len += c.len_utf8();
+    }
+
+    len
+}
+
+/// An iterator over clusters of a text. It is made by [clusters](fn.clusters.html).
+pub struct Clusters<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Iterator for Clusters<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let len = cluster_len(self.text, self.offset);
+
+        if len == 0 {
+            return None
+        }
+
+        let cluster = &self.text[self.offset..self.offset + len];
+        self.offset += len;
+        Some(cluster)
+    }
+}
+
+/// Split given text into Thai character clusters.
+///
+/// Every character that is not Thai is a cluster of its own.
+/// ```
+/// use tokenizer::th::tcc;
+/// assert_eq!(tcc::clusters("เกาะน้ำ").collect::<Vec<&str>>(), vec!["เกาะ", "น้ำ"]);
+/// ```
+pub fn clusters(text: &str) -> Clusters<'_> {
+    Clusters {
+        text,
+        offset: 0
+    }
+}
+
+/// Return a `Vec` of `text.len() + 1` flags. A flag at any byte offset is true if the offset
+/// is an edge of a cluster. The first and last flags are always true.
+pub(crate) fn boundaries(text: &str) -> Vec<bool> {
+    let mut edges = vec![false; text.len() + 1];
+    let mut offset = 0;
+    edges[0] = true;
+
+    while offset < text.len() {
+        offset += cluster_len(text, offset);
+        edges[offset] = true;
+    }
+
+    edges
+}
+
+/// Return true if given cluster has a vowel, either leading, following, above or below vowel.
+#[inline(always)]
+fn has_vowel(cluster: &str) -> bool {
+    cluster.chars().any(|c| matches!(c, 'ะ'..='ู' | 'เ'..='ไ' | '็'))
+}
+
+/// Return true if given cluster is only consonants, possibly with thanthakhat.
+#[inline(always)]
+fn is_bare(cluster: &str) -> bool {
+    cluster.chars().next().is_some_and(is_consonant) && !has_vowel(cluster)
+}
+
+/// An iterator over syllables of a text. It is made by [syllables](fn.syllables.html).
+pub struct Syllables<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Iterator for Syllables<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let start = self.offset;
+        let first = cluster_len(self.text, start);
+
+        if first == 0 {
+            return None
+        }
+
+        self.offset += first;
+
+        if is_bare(&self.text[start..self.offset]) || has_vowel(&self.text[start..self.offset]) {
+            // Take every following consonant. They are final consonants of this syllable or
+            // consonants with inherent vowel.
+            loop {
+                let len = cluster_len(self.text, self.offset);
+
+                if len == 0 || !is_bare(&self.text[self.offset..self.offset + len]) {
+                    break
+                }
+
+                self.offset += len;
+            }
+        }
+
+        Some(&self.text[start..self.offset])
+    }
+}
+
+/// Split given text into approximated Thai syllables.
+///
+/// A syllable is a cluster that has a vowel along with every following cluster that is only consonants.
+/// Consonants that come before any vowel form a syllable of their own. It doesn't need dictionary so
+/// it cannot tell a syllable with inherent vowel, e.g. `ขนม` is a single syllable.
+/// ```
+/// use tokenizer::th::tcc;
+/// assert_eq!(tcc::syllables("กรรมการบ้าน").collect::<Vec<&str>>(), vec!["กรรม", "การ", "บ้าน"]);
+/// ```
+pub fn syllables(text: &str) -> Syllables<'_> {
+    Syllables {
+        text,
+        offset: 0
+    }
+}
+
+/// Split text by white space then split each word into units by given function.
+///
+/// Only Thai and other unclassified part of each word is split by `units`. A number, punctuation
+/// or Latin word is kept whole. It return byte range and kind of each unit.
+fn unit_ranges<'a, F, I>(text: &'a str, units: F) -> Vec<(usize, usize, TokenKind)> where F: Fn(&'a str) -> I, I: Iterator<Item=&'a str> {
+    let mut ranges = Vec::new();
+
+    for word in text.split_whitespace() {
+        let word_offset = subslice_offset(text, word);
+
+        for (start, end, kind) in split_kinds(word) {
+            let (start, end) = (word_offset + start, word_offset + end);
+
+            if kind == TokenKind::Unknown {
+                ranges.extend(units(&text[start..end]).map(|unit| {
+                    let start = subslice_offset(text, unit);
+                    (start, start + unit.len(), TokenKind::Unknown)
+                }));
+            } else {
+                ranges.push((start, end, kind));
+            }
+        }
+    }
+
+    ranges
+}
+
+/// A tokenizer that split Thai text into Thai character clusters.
+///
+/// It doesn't need any dictionary. Text is split by white space first. A number, punctuation
+/// or Latin word is a single token. The rest is split by [clusters](fn.clusters.html) and
+/// each cluster has kind `Unknown`.
+/// ```
+/// use tokenizer::{Tokenizer, th};
+/// assert_eq!(th::ClusterTokenizer.tokenize("เกาะน้ำ 12"), vec!["เกาะ", "น้ำ", "12"]);
+/// ```
+pub struct ClusterTokenizer;
+
+impl crate::tokenizer::Tokenizer for ClusterTokenizer {
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        unit_ranges(text, clusters).into_iter().map(|(start, end, _)| &text[start..end]).collect()
+    }
+}
+
+impl crate::tokenizer::SpanTokenizer for ClusterTokenizer {
+    fn tokenize_spans<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        make_tokens(text, unit_ranges(text, clusters))
+    }
+}
+
+/// A tokenizer that split Thai text into approximated syllables.
+///
+/// It is the same as [ClusterTokenizer](struct.ClusterTokenizer.html) except that Thai text
+/// is split by [syllables](fn.syllables.html).
+/// ```
+/// use tokenizer::{Tokenizer, th};
+/// assert_eq!(th::SyllableTokenizer.tokenize("การบ้าน easy"), vec!["การ", "บ้าน", "easy"]);
+/// ```
+pub struct SyllableTokenizer;
+
+impl crate::tokenizer::Tokenizer for SyllableTokenizer {
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        unit_ranges(text, syllables).into_iter().map(|(start, end, _)| &text[start..end]).collect()
+    }
+}
+
+impl crate::tokenizer::SpanTokenizer for SyllableTokenizer {
+    fn tokenize_spans<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        make_tokens(text, unit_ranges(text, syllables))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,99 @@
+use super::*;
+
+fn split(text: &str) -> Vec<&str> {
+    clusters(text).collect()
+}
+
+#[test]
+fn test_leading_vowel() {
+    assert_eq!(split("เกาะ"), vec!["เกาะ"]);
+    assert_eq!(split("เด็ก"), vec!["เด็ก"]);
+    assert_eq!(split("เพราะ"), vec!["เพราะ"]);
+    assert_eq!(split("เมื่อ"), vec!["เมื่อ"]);
+    assert_eq!(split("เสีย"), vec!["เสีย"]);
+    assert_eq!(split("แข็ง"), vec!["แข็ง"]);
+    assert_eq!(split("ไม่ใช่"), vec!["ไม่", "ใช่"]);
+    // A leading vowel is never separated from its consonants
+    assert_eq!(split("เพลง"), vec!["เพล", "ง"]);
+}
+
+#[test]
+fn test_sara_am() {
+    assert_eq!(split("กำลัง"), vec!["กำ", "ลัง"]);
+    assert_eq!(split("น้ำ"), vec!["น้ำ"]);
+    assert_eq!(split("ทำนา"), vec!["ทำ", "นา"]);
+}
+
+#[test]
+fn test_vowel_and_tone() {
+    assert_eq!(split("ที่นี่"), vec!["ที่", "นี่"]);
+    assert_eq!(split("บ้าน"), vec!["บ้า", "น"]);
+    assert_eq!(split("คือ"), vec!["คือ"]);
+    assert_eq!(split("กัน"), vec!["กัน"]);
+}
+
+#[test]
+fn test_thanthakhat() {
+    assert_eq!(split("จันทร์"), vec!["จันทร์"]);
+    assert_eq!(split("ศิลป์"), vec!["ศิลป์"]);
+    assert_eq!(split("อาจารย์"), vec!["อา", "จารย์"]);
+}
+
+#[test]
+fn test_non_thai() {
+    assert_eq!(split("ab ๑2ๆ"), vec!["a", "b", " ", "๑", "2", "ๆ"]);
+    // A dangling dependent char is a cluster of its own
+    assert_eq!(split("่ก"), vec!["่", "ก"]);
+    // A dependent vowel or tone mark is only attached to a Thai consonant
+    assert_eq!(split("\u{200b}า"), vec!["\u{200b}", "า"]);
+    assert_eq!(split("a่ๆา"), vec!["a", "่", "ๆ", "า"]);
+    assert_eq!(split("เา"), vec!["เ", "า"]);
+    assert_eq!(split(""), Vec::<&str>::new());
+}
+
+#[test]
+fn test_boundaries() {
+    let text = "กำลัง";
+    let edges = boundaries(text);
+    assert_eq!(edges.len(), text.len() + 1);
+    assert_eq!(edges.iter().enumerate().filter(|(_, e)| **e).map(|(i, _)| i).collect::<Vec<_>>(), vec![0, 6, 15]);
+}
+
+#[test]
+fn test_syllables() {
+    let split = |text| syllables(text).collect::<Vec<&str>>();
+    assert_eq!(split("กรรมการบ้าน"), vec!["กรรม", "การ", "บ้าน"]);
+    assert_eq!(split("กำลังเพลง"), vec!["กำ", "ลัง", "เพลง"]);
+    assert_eq!(split("คนดีมากๆ"), vec!["คน", "ดี", "มาก", "ๆ"]);
+    assert_eq!(split("จันทร์"), vec!["จันทร์"]);
+}
+
+#[test]
+fn test_cluster_tokenizer() {
+    use crate::tokenizer::{SpanTokenizer, Tokenizer};
+    let text = "น้ำ  COVID 1,900 ๑๒เกาะ";
+    assert_eq!(ClusterTokenizer.tokenize(text), vec!["น้ำ", "COVID", "1,900", "๑๒", "เกาะ"]);
+
+    let tokens = ClusterTokenizer.tokenize_spans(text);
+    assert_eq!(
+        tokens.iter().map(|t| (t.text, t.start, t.char_offset, t.kind)).collect::<Vec<_>>(),
+        vec![
+            ("น้ำ", 0, 0, TokenKind::Unknown),
+            ("COVID", 11, 5, TokenKind::Latin),
+            ("1,900", 17, 11, TokenKind::Number),
+            ("๑๒", 23, 17, TokenKind::Number),
+            ("เกาะ", 29, 19, TokenKind::Unknown),
+        ]
+    );
+}
+
+#[test]
+fn test_syllable_tokenizer() {
+    use crate::tokenizer::{SpanTokenizer, Tokenizer};
+    let text = "การบ้าน easy, กรรมการ";
+    assert_eq!(SyllableTokenizer.tokenize(text), vec!["การ", "บ้าน", "easy", ",", "กรรม", "การ"]);
+    assert_eq!(
+        SyllableTokenizer.tokenize_spans(text).iter().map(|t| t.text).collect::<Vec<_>>(),
+        SyllableTokenizer.tokenize(text)
+    );
+}
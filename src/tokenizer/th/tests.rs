@@ -1,12 +1,16 @@
+use crate::dict::tests::frequency_dict;
 use crate::tokenizer::{Tokenizer};
 
+/// Words of `ตากลม` where `ตา` `กลม` is more frequent than `ตาก` `ลม`.
+const TA_KLOM: [(&str, u64); 4] = [("ตา", 100), ("กลม", 50), ("ตาก", 5), ("ลม", 60)];
+
 #[test]
 fn test_all_matched() {
     use std::io::{BufRead, BufReader};
     let file = BufReader::new(std::fs::File::open("data/th.txt").unwrap());
     let sources: Vec<String> = file.lines().map(|l| l.unwrap()).collect();
     let concatenated = sources.iter().fold("".to_owned(), |mut acc, val| {
-        acc.push_str(&val);
+        acc.push_str(val);
         acc
     });
 
@@ -42,7 +46,7 @@ fn test_all_possible_triplet() {
                                 .map(|triplet| (
                                     triplet.iter()
                                             .fold("".to_owned(), |mut acc, val| {
-                                                acc.push_str(&val); 
+                                                acc.push_str(val); 
                                                 acc
                                             }), 
                                     triplet));
@@ -85,4 +89,462 @@ fn test_init_by_slice() {
     let sources: Vec<String> = file.lines().map(|l| l.unwrap()).collect();
     let tokenizer = super::Tokenizer::from(sources.as_slice());
     assert_eq!(vec!["การบ้าน", "กรรมกร"], tokenizer.tokenize("การบ้านกรรมกร"));
-}
\ No newline at end of file
+}
+#[test]
+fn test_tokenize_spans() {
+    use crate::tokenizer::SpanTokenizer;
+    let tokenizer = super::Tokenizer::new("data/th.txt").unwrap();
+    let text = "การบ้าน  easy มากๆ กรรมกรเอา";
+    let tokens = tokenizer.tokenize_spans(text);
+    assert_eq!(
+        tokens.iter().map(|t| (t.text, t.start, t.end, t.char_offset, t.index)).collect::<Vec<_>>(),
+        vec![
+            ("การบ้าน", 0, 21, 0, 0),
+            ("easy", 23, 27, 9, 1),
+            ("มากๆ", 28, 40, 14, 2),
+            ("กรรมกร", 41, 59, 19, 3),
+            ("เอา", 59, 68, 25, 4),
+        ]
+    );
+    tokens.iter().for_each(|t| assert_eq!(&text[t.start..t.end], t.text));
+    assert_eq!(tokens.iter().map(|t| t.text).collect::<Vec<_>>(), tokenizer.tokenize(text));
+}
+
+#[test]
+fn test_token_kinds() {
+    use crate::tokenizer::{SpanTokenizer, TokenKind};
+    let tokenizer = super::Tokenizer::new("data/th.txt").unwrap();
+    let tokens = tokenizer.tokenize_spans("เอากรรมกร ๑๒คน, easy 3.5การบ้าน");
+    assert_eq!(
+        tokens.iter().map(|t| (t.text, t.kind)).collect::<Vec<_>>(),
+        vec![
+            ("เอา", TokenKind::Known),
+            ("กรรมกร", TokenKind::Known),
+            ("๑๒", TokenKind::Number),
+            ("คน", TokenKind::Unknown),
+            (",", TokenKind::Punctuation),
+            ("easy", TokenKind::Latin),
+            ("3.5", TokenKind::Number),
+            ("การบ้าน", TokenKind::Known),
+        ]
+    );
+
+    // A Latin word keep its digits and punctuation but other unknown word doesn't
+    let tokens = tokenizer.tokenize_spans("ไทย (COVID-19), U.S.A. 3.5%");
+    assert_eq!(
+        tokens.iter().map(|t| (t.text, t.kind)).collect::<Vec<_>>(),
+        vec![
+            ("ไทย", TokenKind::Unknown),
+            ("(COVID-19),", TokenKind::Latin),
+            ("U.S.A.", TokenKind::Latin),
+            ("3.5", TokenKind::Number),
+            ("%", TokenKind::Punctuation),
+        ]
+    );
+}
+
+#[test]
+fn test_from_compiled() {
+    let path = std::env::temp_dir().join(format!("tokenizer_test_from_compiled_{}.bin", std::process::id()));
+    let tokenizer = super::Tokenizer::new("data/th.txt").unwrap();
+    tokenizer.save_compiled(&path).unwrap();
+    let compiled = super::Tokenizer::from_compiled(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let input = "เอากรรมกรที่เอาการเอางาน";
+    assert_eq!(compiled.unwrap().tokenize(input), tokenizer.tokenize(input));
+
+    // A missing or invalid file is an error with its path
+    let err = super::Tokenizer::from_compiled(&path).err().unwrap();
+    assert!(matches!(&err, crate::Error::Io {path: Some(p), ..} if p == &path));
+    let err = super::Tokenizer::from_compiled("data/th.txt").err().unwrap();
+    assert!(err.to_string().starts_with("data/th.txt: "));
+    assert_eq!(std::io::Error::from(err).kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[cfg(feature="mmap")]
+#[test]
+fn test_from_mmap() {
+    let path = std::env::temp_dir().join(format!("tokenizer_test_from_mmap_{}.bin", std::process::id()));
+    let tokenizer = super::Tokenizer::new("data/th.txt").unwrap();
+    tokenizer.save_compiled(&path).unwrap();
+    let mapped = super::Tokenizer::from_mmap(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let input = "เอากรรมกรที่เอาการเอางาน";
+    assert_eq!(mapped.tokenize(input), tokenizer.tokenize(input));
+}
+
+#[cfg(feature="bundled-dict")]
+#[test]
+fn test_bundled() {
+    let tokenizer = super::Tokenizer::default();
+    assert!(tokenizer.dict().len() > 1000);
+    assert_eq!(tokenizer.tokenize("วันนี้อากาศร้อนมาก"), vec!["วันนี้", "อากาศ", "ร้อน", "มาก"]);
+    assert_eq!(tokenizer.tokenize("ฉันชอบกินข้าวผัด"), vec!["ฉัน", "ชอบ", "กิน", "ข้าวผัด"]);
+}
+
+#[test]
+fn test_dict() {
+    let tokenizer = super::Tokenizer::new("data/th.txt").unwrap();
+
+    assert_eq!(tokenizer.dict().len(), 12);
+    assert!(tokenizer.dict().contains("อาจารย์"));
+    assert!(!tokenizer.dict().contains("ที่"));
+}
+
+#[test]
+fn test_unigram() {
+    use crate::dict::SizedDict;
+    use super::Mode;
+
+    fn make_tokenizer(words: &[(&str, u64)]) -> super::Tokenizer<SizedDict<u64>> {
+        super::Tokenizer::with_dict(frequency_dict(words)).with_mode(Mode::Unigram)
+    }
+
+    // Both paths have two words so the frequency decide
+    let tokenizer = make_tokenizer(&TA_KLOM);
+    assert_eq!(tokenizer.mode(), Mode::Unigram);
+    assert_eq!(tokenizer.tokenize("ตากลม"), vec!["ตา", "กลม"]);
+    let tokenizer = make_tokenizer(&[("ตา", 5), ("กลม", 50), ("ตาก", 100), ("ลม", 60)]);
+    assert_eq!(tokenizer.tokenize("ตากลม"), vec!["ตาก", "ลม"]);
+
+    // Rare compound word lose to common split words
+    let tokenizer = make_tokenizer(&[("กรรม", 120), ("การ", 500), ("กรรมการ", 1)]);
+    assert_eq!(tokenizer.tokenize("กรรมการ"), vec!["กรรม", "การ"]);
+    let tokenizer = make_tokenizer(&[("กรรม", 120), ("การ", 500), ("กรรมการ", 80), ("เอา", 300)]);
+    assert_eq!(tokenizer.tokenize("กรรมการ"), vec!["กรรมการ"]);
+
+    // Unknown chars are merged into one word
+    assert_eq!(tokenizer.tokenize("ขกรรมการคงabcการ"), vec!["ข", "กรรมการ", "คงabc", "การ"]);
+}
+
+#[test]
+fn test_unigram_tsv() {
+    use crate::dict::{Dict, SizedDict};
+    use crate::tokenizer::{SpanTokenizer, TokenKind};
+    let dict = SizedDict::from(Dict::load_tsv("data/th.tsv").unwrap());
+    let tokenizer = super::Tokenizer::with_dict(dict).with_mode(super::Mode::Unigram);
+    let tokens = tokenizer.tokenize_spans("เอากรรมการบ้าน 12");
+    assert_eq!(
+        tokens.iter().map(|t| (t.text, t.kind)).collect::<Vec<_>>(),
+        vec![
+            ("เอา", TokenKind::Known),
+            ("กรรม", TokenKind::Known),
+            ("การบ้าน", TokenKind::Known),
+            ("12", TokenKind::Number),
+        ]
+    );
+}
+
+#[test]
+fn test_unigram_compiled() {
+    use crate::dict::{Dict, DictView, SizedDict};
+    use crate::tokenizer::SpanTokenizer;
+    use super::Mode;
+    // Frequency in binary dictionary shall drive unigram the same way as the source dictionary
+    let dict = SizedDict::from(Dict::load_tsv("data/th.tsv").unwrap());
+    let bytes = dict.to_bytes();
+    let path = std::env::temp_dir().join(format!("tokenizer_test_unigram_compiled_{}.bin", std::process::id()));
+    dict.save(&path).unwrap();
+    let expected = super::Tokenizer::with_dict(dict).with_mode(Mode::Unigram).tokenize_spans("เอากรรมการบ้าน 12");
+    assert_eq!(expected.iter().map(|t| t.text).collect::<Vec<_>>(), vec!["เอา", "กรรม", "การบ้าน", "12"]);
+
+    let view = DictView::new(&bytes).unwrap();
+    let tokenizer = super::Tokenizer::with_dict(view).with_mode(Mode::Unigram);
+    assert_eq!(tokenizer.tokenize_spans("เอากรรมการบ้าน 12"), expected);
+
+    let tokenizer = super::Tokenizer::from_compiled(&path).unwrap().with_mode(Mode::Unigram);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(tokenizer.tokenize_spans("เอากรรมการบ้าน 12"), expected);
+
+    // Both paths have two words so only frequency decide
+    for (words, expected) in [
+        (TA_KLOM, ["ตา", "กลม"]),
+        ([("ตา", 5), ("กลม", 50), ("ตาก", 100), ("ลม", 60)], ["ตาก", "ลม"]),
+    ].iter() {
+        let bytes = frequency_dict(words).to_bytes();
+        let tokenizer = super::Tokenizer::with_dict(DictView::new(&bytes).unwrap()).with_mode(Mode::Unigram);
+        assert_eq!(tokenizer.tokenize("ตากลม"), expected);
+    }
+}
+
+#[test]
+fn test_cluster_boundary() {
+    let words = ["เก", "กา", "ดี"];
+    let tokenizer = super::Tokenizer::from(&words[..]);
+    // "เก" is a prefix of "เกาะ" but it end in the middle of cluster
+    assert_eq!(tokenizer.tokenize("เกาะเก"), vec!["เกาะ", "เก"]);
+    // Unknown word never end between consonant and its vowel
+    assert_eq!(tokenizer.tokenize("ที่ดีกา"), vec!["ที่", "ดี", "กา"]);
+    assert_eq!(tokenizer.tokenize("กำกา"), vec!["กำ", "กา"]);
+
+    let tokenizer = tokenizer.with_mode(super::Mode::Unigram);
+    assert_eq!(tokenizer.tokenize("เกาะเก"), vec!["เกาะ", "เก"]);
+    assert_eq!(tokenizer.tokenize("กำกา"), vec!["กำ", "กา"]);
+}
+
+#[test]
+fn test_policy() {
+    use super::Policy;
+    // "กขค" followed by unknown "ง" or "ก", "ข" and "คง" without unknown
+    let words = ["ก", "ข", "คง", "กขค", "กข"];
+    let tokenizer = super::Tokenizer::from(&words[..]);
+    assert_eq!(tokenizer.policy(), Policy::FewestTokens);
+    assert_eq!(tokenizer.tokenize("กขคง"), vec!["กข", "คง"]);
+
+    let tokenizer = tokenizer.with_policy(Policy::LongestFirstWord);
+    assert_eq!(tokenizer.tokenize("กขคง"), vec!["กขค", "ง"]);
+
+    let words = ["ก", "ข", "คง", "กขค"];
+    let tokenizer = super::Tokenizer::from(&words[..]);
+    assert_eq!(tokenizer.tokenize("กขคง"), vec!["กขค", "ง"]);
+    let tokenizer = tokenizer.with_policy(Policy::FewestUnknown);
+    assert_eq!(tokenizer.tokenize("กขคง"), vec!["ก", "ข", "คง"]);
+}
+
+#[test]
+fn test_single_best_path() {
+    use crate::dict::{Dict, SizedDict};
+    use super::{Policy, Ranking};
+
+    let dict = SizedDict::from(Dict::load_tsv("data/th.tsv").unwrap());
+    let texts = ["เอากรรมการบ้านกรรมกร", "อาจารย์xyzกรรมการเอาการเอางาน", "กรณ์กรรมการบ้านการ", "ขขขกรรม", ""];
+    let policies = [Policy::FewestTokens, Policy::FewestUnknown, Policy::LongestFirstWord, Policy::HighestFrequency];
+
+    // Keeping only the best path from each vertex find the same path as n-best search
+    for text in texts.iter() {
+        for policy in policies.iter() {
+            let (_, expected) = super::best_paths(&dict, text, 1, Ranking::Policy(*policy), 0.0).pop().unwrap();
+            assert_eq!(super::maximal_matching(&dict, text, *policy), expected, "{} {:?}", text, policy);
+        }
+    }
+}
+
+#[test]
+fn test_policy_frequency() {
+    use super::Policy;
+
+    let tokenizer = super::Tokenizer::with_dict(frequency_dict(&TA_KLOM));
+    assert_eq!(tokenizer.tokenize("ตากลม"), vec!["ตาก", "ลม"]);
+
+    let tokenizer = tokenizer.with_policy(Policy::HighestFrequency);
+    assert_eq!(tokenizer.tokenize("ตากลม"), vec!["ตา", "กลม"]);
+}
+
+#[test]
+fn test_n_best() {
+    use crate::tokenizer::{SpanTokenizer, TokenKind};
+
+    let tokenizer = super::Tokenizer::with_dict(frequency_dict(&[&TA_KLOM[..], &[("ตากลม", 1)]].concat()));
+    fn texts<'a>(segmentations: &[super::Segmentation<'a>]) -> Vec<Vec<&'a str>> {
+        segmentations.iter().map(|s| s.tokens.iter().map(|t| t.text).collect()).collect()
+    }
+
+    let best = tokenizer.n_best("ตากลม", 5);
+    assert_eq!(texts(&best), vec![vec!["ตากลม"], vec!["ตาก", "ลม"], vec!["ตา", "กลม"]]);
+    assert_eq!(best.iter().map(|s| s.word_count).collect::<Vec<_>>(), vec![1, 2, 2]);
+    assert_eq!(best[0].tokens, tokenizer.tokenize_spans("ตากลม"));
+    // ln(1 / 216), ln(5 / 216) + ln(60 / 216) and ln(100 / 216) + ln(50 / 216)
+    let total = 216f64.ln();
+    assert!((best[0].score - (0.0 - total)).abs() < 1e-9);
+    assert!((best[1].score - (5f64.ln() + 60f64.ln() - 2.0 * total)).abs() < 1e-9);
+    assert!((best[2].score - (100f64.ln() + 50f64.ln() - 2.0 * total)).abs() < 1e-9);
+
+    // Unigram mode order by score
+    let tokenizer = tokenizer.with_mode(super::Mode::Unigram);
+    assert_eq!(texts(&tokenizer.n_best("ตากลม", 2)), vec![vec!["ตา", "กลม"], vec!["ตาก", "ลม"]]);
+
+    // Every combination of chunks separated by white space along with unknown word
+    let best = tokenizer.n_best("ตากลม xyzลม", 4);
+    assert_eq!(texts(&best), vec![
+        vec!["ตา", "กลม", "xyz", "ลม"],
+        vec!["ตาก", "ลม", "xyz", "ลม"],
+        vec!["ตากลม", "xyz", "ลม"],
+    ]);
+    assert!(best.iter().all(|s| s.unknown_bytes == 3));
+    assert_eq!(best[0].tokens[2].kind, TokenKind::Latin);
+    assert_eq!(best[0].tokens[3].start, 19);
+
+    assert!(tokenizer.n_best("ตากลม", 0).is_empty());
+}
+
+#[test]
+fn test_result_tree() {
+    use crate::tokenizer::{Tree, TreeOp};
+
+    let words = ["ตา", "กลม", "ตาก", "ลม", "ตากลม"];
+    let tokenizer = super::Tokenizer::from(&words[..]);
+    let dict = tokenizer.dict();
+    fn word_texts<'a>(tree: &Tree<(&'a str, bool)>, leaf: &super::LeafNode) -> Vec<&'a str> {
+        tree.to_vec(leaf.node).into_iter().map(|(word, _)| word).collect()
+    }
+
+    let root = [super::LeafNode {node: Tree::<(&str, bool)>::ROOT, score: Default::default()}];
+    let mut tree = Tree::new();
+    let mut leaves = Vec::new();
+    super::make_result_tree(dict, "ตากลมxตา", &mut tree, &root, &mut leaves, 0.0);
+    assert_eq!(
+        leaves.iter().map(|l| (word_texts(&tree, l), l.score.unknown_bytes)).collect::<Vec<_>>(),
+        vec![
+            (vec!["ตากลม", "x", "ตา"], 1),
+            (vec!["ตา", "กลม", "x", "ตา"], 1),
+            (vec!["ตาก", "ลม", "x", "ตา"], 1),
+        ]
+    );
+    assert_eq!(tree.to_vec(leaves[0].node), vec![("ตากลม", true), ("x", false), ("ตา", true)]);
+
+    // Paragraph-sized text with 3 ^ 4 segmentations
+    let text = "ตากลมการบ้าน".repeat(4) + &"ตา".repeat(2_000);
+    let mut tree = Tree::new();
+    let mut leaves = Vec::new();
+    super::make_result_tree(dict, &text, &mut tree, &root, &mut leaves, 0.0);
+    assert_eq!(leaves.len(), 81);
+    assert!(leaves.iter().all(|l| word_texts(&tree, l).concat() == text));
+}
+
+#[test]
+fn test_segmentations() {
+    use crate::tokenizer::TokenKind;
+
+    // Mode is not used by segmentations but it make n_best ordered by the same score
+    let tokenizer = super::Tokenizer::with_dict(frequency_dict(&TA_KLOM)).with_mode(super::Mode::Unigram);
+
+    let mut segmentations = tokenizer.segmentations("ตากลม ลมx").collect::<Vec<_>>();
+    assert_eq!(segmentations.len(), 2);
+    segmentations.sort_by(|a, b| b.score.total_cmp(&a.score));
+    assert_eq!(
+        segmentations.iter().map(|s| s.tokens.iter().map(|t| t.text).collect::<Vec<_>>()).collect::<Vec<_>>(),
+        vec![vec!["ตา", "กลม", "ลม", "x"], vec!["ตาก", "ลม", "ลม", "x"]]
+    );
+    assert_eq!(segmentations[0].tokens[3].kind, TokenKind::Latin);
+    assert_eq!((segmentations[0].word_count, segmentations[0].unknown_bytes), (4, 1));
+    assert_eq!(segmentations[0].tokens[2].start, 16);
+
+    // The same segmentations and scores as n_best
+    assert_eq!(tokenizer.n_best("ตากลม ลมx", 5), segmentations);
+
+    let empty = tokenizer.segmentations(" ").collect::<Vec<_>>();
+    assert_eq!(empty.len(), 1);
+    assert!(empty[0].tokens.is_empty());
+
+    // Enumerating every segmentation of paragraph-sized text is practical
+    let text = "ตากลม ".repeat(10) + &"ตา".repeat(200);
+    assert_eq!(tokenizer.segmentations(&text).count(), 1024);
+}
+
+#[test]
+fn test_tokenize_iter() {
+    let tokenizer = super::Tokenizer::from(&["กำลัง", "การ", "บ้าน", "การบ้าน", "ทำ"][..]);
+    let text = "กำลังทำการบ้าน  12 ทำการบ้าน";
+    let expected = tokenizer.tokenize(text);
+    assert_eq!(tokenizer.tokenize_iter(text).collect::<Vec<_>>(), expected);
+    assert_eq!(tokenizer.tokenize_iter(text).take(2).collect::<Vec<_>>(), vec!["กำลัง", "ทำ"]);
+    assert_eq!(tokenizer.tokenize_iter("").next(), None);
+
+    // The same buffer can be reused and previous tokens are kept
+    let mut tokens = vec!["x"];
+    tokenizer.tokenize_into(text, &mut tokens);
+    assert_eq!(tokens[0], "x");
+    assert_eq!(&tokens[1..], &expected[..]);
+    tokens.clear();
+    tokenizer.tokenize_into("ทำการบ้าน", &mut tokens);
+    assert_eq!(tokens, vec!["ทำ", "การบ้าน"]);
+}
+
+#[test]
+fn test_parallelism() {
+    use crate::tokenizer::Parallelism;
+
+    let words = ["กำลัง", "การ", "บ้าน", "การบ้าน", "ทำ"];
+    let text = "กำลังทำการบ้าน  12 ทำการบ้าน COVID-19 ".repeat(50);
+    let sequential = super::Tokenizer::from(&words[..]).with_parallelism(Parallelism::Sequential);
+    let expected = sequential.tokenize(&text);
+    assert_eq!(expected.len(), 350);
+    assert_eq!(sequential.tokenize_iter(&text).collect::<Vec<_>>(), expected);
+
+    #[cfg(feature="multi-thread")]
+    {
+        use crate::tokenizer::SpanTokenizer;
+
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(3).build().unwrap();
+        for parallelism in [Parallelism::Global, Parallelism::Pool(std::sync::Arc::new(pool))] {
+            let tokenizer = super::Tokenizer::from(&words[..]).with_parallelism(parallelism);
+            assert_eq!(tokenizer.tokenize(&text), expected);
+            assert_eq!(tokenizer.tokenize_spans(&text), sequential.tokenize_spans(&text));
+        }
+    }
+}
+
+#[test]
+fn test_tokenize_batch() {
+    use crate::tokenizer::Parallelism;
+
+    let words = ["กำลัง", "การ", "บ้าน", "การบ้าน", "ทำ"];
+    let documents: Vec<String> = (0..1000).map(|i| format!("ทำการบ้าน {} กำลังทำ", i)).collect();
+    let expected: Vec<Vec<&str>> = documents.iter().map(|d| super::Tokenizer::from(&words[..]).tokenize(d)).collect();
+    assert_eq!(expected[7], vec!["ทำ", "การบ้าน", "7", "กำลัง", "ทำ"]);
+
+    let tokenizer = super::Tokenizer::from(&words[..]).with_parallelism(Parallelism::Sequential);
+    assert_eq!(tokenizer.tokenize_batch(&documents), expected);
+    assert!(tokenizer.tokenize_batch::<&str>(&[]).is_empty());
+
+    #[cfg(feature="multi-thread")]
+    {
+        use rayon::prelude::*;
+
+        let tokenizer = super::Tokenizer::from(&words[..]).with_batch_min_len(64);
+        assert_eq!(tokenizer.batch_min_len(), 64);
+        assert_eq!(tokenizer.tokenize_batch(&documents), expected);
+
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(3).build().unwrap();
+        let tokenizer = tokenizer.with_parallelism(Parallelism::Pool(std::sync::Arc::new(pool)));
+        assert_eq!(tokenizer.tokenize_batch(&documents), expected);
+        assert_eq!(tokenizer.par_tokenize_batch(documents.par_iter().map(String::as_str)), expected);
+    }
+}
+
+#[test]
+fn test_separators() {
+    use crate::tokenizer::{Parallelism, SpanTokenizer, TokenKind};
+    use super::Separators;
+
+    let words = ["กำลัง", "การ", "บ้าน", "การบ้าน", "ทำ", "ฯลฯ", "ทำฯ"];
+    let text = " กำลังทำการบ้าน\u{3000} ทำฯลฯ (COVID-19), 1,000.\n";
+    let tokenizer = super::Tokenizer::from(&words[..]);
+    assert_eq!(tokenizer.separators(), Separators::Discard);
+    assert_eq!(
+        tokenizer.tokenize(text),
+        vec!["กำลัง", "ทำ", "การบ้าน", "ทำ", "ฯลฯ", "(COVID-19),", "1,000", "."]
+    );
+
+    let tokenizer = super::Tokenizer::from(&words[..]).with_separators(Separators::Whitespace);
+    let expected = vec![
+        " ", "กำลัง", "ทำ", "การบ้าน", "\u{3000} ", "ทำ", "ฯลฯ", " ", "(COVID-19),", " ", "1,000", ".", "\n"
+    ];
+    assert_eq!(tokenizer.tokenize(text), expected);
+    assert_eq!(tokenizer.tokenize_iter(text).collect::<Vec<_>>(), expected);
+    assert_eq!(tokenizer.tokenize(text).concat(), text);
+
+    // Punctuation is split out of dictionary word
+    let tokenizer = tokenizer.with_separators(Separators::WhitespaceAndPunctuation);
+    let tokens = tokenizer.tokenize_spans(text);
+    assert_eq!(
+        tokens.iter().skip(4).take(6).map(|t| (t.text, t.kind)).collect::<Vec<_>>(),
+        vec![
+            ("\u{3000} ", TokenKind::Whitespace),
+            ("ทำ", TokenKind::Known),
+            ("ฯ", TokenKind::Punctuation),
+            ("ล", TokenKind::Unknown),
+            ("ฯ", TokenKind::Punctuation),
+            (" ", TokenKind::Whitespace),
+        ]
+    );
+    assert_eq!(tokens.last().map(|t| (t.text, t.kind)), Some(("\n", TokenKind::Whitespace)));
+    assert_eq!(tokens.iter().map(|t| t.text).collect::<String>(), text);
+    assert_eq!(tokenizer.tokenize_iter(text).collect::<Vec<_>>(), tokenizer.tokenize(text));
+
+    let sequential = tokenizer.with_parallelism(Parallelism::Sequential);
+    assert_eq!(sequential.tokenize_spans(text), tokens);
+    assert_eq!(sequential.tokenize(""), Vec::<&str>::new());
+}